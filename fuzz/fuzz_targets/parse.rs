@@ -0,0 +1,27 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use elf_reader::{ELF, ElfFile, ParseMode};
+
+// Untrusted input may be rejected, but must never panic.
+fuzz_target!(|data: &[u8]| {
+    let _ = ELF::<u32>::parse(data);
+    let _ = ELF::<u64>::parse(data);
+    let _ = ELF::<u32>::parse_with_mode(data, ParseMode::Lenient);
+    let _ = ELF::<u64>::parse_with_mode(data, ParseMode::Lenient);
+
+    match ElfFile::new(data.to_vec()) {
+        Some(ElfFile::Elf32(elf)) => {
+            let _ = elf.to_string();
+            let _ = elf.section_iter().count();
+            let _ = elf.notes();
+        }
+        Some(ElfFile::Elf64(elf)) => {
+            let _ = elf.to_string();
+            let _ = elf.section_iter().count();
+            let _ = elf.notes();
+        }
+        None => {}
+    }
+});
@@ -0,0 +1,63 @@
+use core::str;
+use super::prelude::*;
+
+const MAGIC: &[u8] = b"!<arch>\n";
+const MEMBER_HEADER_SIZE: usize = 0x3C;
+
+/// A static archive (`.a`) whose members borrow the archive bytes,
+/// so each object can be handed to `ELF::parse` directly.
+pub struct Archive<'a> {
+    members: Vec<(&'a str, &'a [u8])>,
+}
+
+impl<'a> Archive<'a> {
+    pub fn parse(data: &'a [u8]) -> Option<Archive<'a>> {
+        if !data.starts_with(MAGIC) { return None }
+
+        let mut members = Vec::new();
+        let mut extended_names: &[u8] = &[];
+        let mut offset = MAGIC.len();
+
+        while offset < data.len() {
+            let header = data.get(offset..offset + MEMBER_HEADER_SIZE)?;
+            if &header[0x3A..0x3C] != b"`\n" { return None }
+
+            let size = str::from_utf8(&header[0x30..0x3A]).ok()?.trim_end().parse::<usize>().ok()?;
+            let start = offset + MEMBER_HEADER_SIZE;
+            let body = data.get(start..start.checked_add(size)?)?;
+            let name = str::from_utf8(&header[0x00..0x10]).ok()?.trim_end();
+
+            match name {
+                // symbol lookup tables, not objects
+                "/" | "/SYM64/" => {}
+                "//" => extended_names = body,
+                _ => members.push((member_name(name, extended_names)?, body)),
+            }
+
+            // member bodies are aligned to even offsets
+            offset = start + size + size % 2;
+        }
+
+        Some(Archive { members })
+    }
+
+    /// Iterates over `(name, bytes)` for every member except the symbol
+    /// and extended-name tables.
+    pub fn members(&self) -> impl Iterator<Item = (&'a str, &'a [u8])> + '_ {
+        self.members.iter().cloned()
+    }
+}
+
+fn member_name<'a>(name: &'a str, extended_names: &'a [u8]) -> Option<&'a str> {
+    // GNU stores long names as `/<offset>` into the `//` member,
+    // each entry terminated by `/\n`
+    match name.strip_prefix('/') {
+        Some(index) => {
+            let start = index.parse::<usize>().ok()?;
+            let rest = extended_names.get(start..)?;
+            let end = rest.windows(2).position(|w| w == b"/\n").unwrap_or(rest.len());
+            str::from_utf8(&rest[..end]).ok()
+        }
+        None => Some(name.strip_suffix('/').unwrap_or(name)),
+    }
+}
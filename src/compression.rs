@@ -0,0 +1,68 @@
+use core::fmt;
+use core::mem;
+
+use num::cast;
+use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+
+use super::{ELF, Reader};
+use super::prelude::*;
+
+// `ch_type` values of `Elf_Chdr`
+const ELFCOMPRESS_ZLIB: u32 = 1;
+const ELFCOMPRESS_ZSTD: u32 = 2;
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Returns the contents of the section named `name`, inflated if it is
+    /// `SHF_COMPRESSED`. Uncompressed sections are returned as stored.
+    ///
+    /// `None` if there is no such section, it has no file contents, or its
+    /// compression type is unknown or its data doesn't decompress to `ch_size` bytes.
+    pub fn section_data_decompressed(&self, name: &str) -> Option<Vec<u8>> {
+        let section = self.section_headers.iter().find(|sh| sh.name() == name)?;
+        let bytes = self.data().get(section.file_range(self.data().len())?)?;
+        if !section.is_compressed() { return Some(bytes.to_vec()) }
+
+        // Elf64_Chdr has a reserved word after ch_type and 64-bit sizes
+        let mut reader = Reader::new(bytes, self.header.is_little());
+        let compression_type = reader.u32().ok()?;
+        let size = if mem::size_of::<T>() == 4 {
+            let size = reader.u32().ok()? as usize;
+            reader.u32().ok()?;
+            size
+        } else {
+            reader.u32().ok()?;
+            let size = reader.u64().ok()? as usize;
+            reader.u64().ok()?;
+            size
+        };
+        let compressed = &bytes[reader.position()..];
+
+        let data = match compression_type {
+            ELFCOMPRESS_ZLIB => miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(compressed, size).ok()?,
+            ELFCOMPRESS_ZSTD => zstd_decompress_with_limit(compressed, size)?,
+            _ => return None,
+        };
+
+        if data.len() == size { Some(data) } else { None }
+    }
+}
+
+// `ch_size` comes from the file, so rather than allocating it up front the
+// output grows a block at a time and decoding stops once it passes `limit`
+fn zstd_decompress_with_limit(mut compressed: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut decoder = FrameDecoder::new();
+    decoder.init(&mut compressed).ok()?;
+
+    let mut data = Vec::new();
+    while !decoder.is_finished() {
+        decoder.decode_blocks(&mut compressed, BlockDecodingStrategy::UptoBlocks(1)).ok()?;
+        if let Some(chunk) = decoder.collect() { data.extend_from_slice(&chunk) }
+        if data.len() > limit { return None }
+    }
+    if let Some(chunk) = decoder.collect() { data.extend_from_slice(&chunk) }
+
+    Some(data)
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, ISA, ObjectFileType};
+use super::{make_u16, make_u32, make_u64};
+use super::prelude::*;
+
+const NT_PRSTATUS: u32 = 1;
+const NT_PRPSINFO: u32 = 3;
+
+// `user_regs_struct` field order on x86_64
+const X86_64_REGISTERS: [&str; 27] = [
+    "r15", "r14", "r13", "r12", "rbp", "rbx", "r11", "r10", "r9", "r8",
+    "rax", "rcx", "rdx", "rsi", "rdi", "orig_rax", "rip", "cs", "eflags", "rsp",
+    "ss", "fs_base", "gs_base", "ds", "es", "fs", "gs",
+];
+
+/// Process state recorded in a core dump's `CORE` notes.
+///
+/// Only the x86_64 layouts of `elf_prpsinfo` and `elf_prstatus` are decoded;
+/// on other architectures the raw descriptors are still available.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreInfo {
+    process_name: Option<String>,
+    arguments: Option<String>,
+    pid: Option<u32>,
+    signal: Option<u16>,
+    registers: Option<Vec<(&'static str, u64)>>,
+    prstatus: Vec<u8>,
+    prpsinfo: Vec<u8>,
+}
+
+impl CoreInfo {
+    /// `pr_fname`: the executable name, truncated to 15 bytes by the kernel.
+    pub fn process_name(&self) -> Option<String> { self.process_name.clone() }
+    /// `pr_psargs`: the start of the command line.
+    pub fn arguments(&self) -> Option<String> { self.arguments.clone() }
+    pub fn pid(&self) -> Option<u32> { self.pid }
+    /// The signal that terminated the first thread.
+    pub fn signal(&self) -> Option<u16> { self.signal }
+    /// General purpose registers of the first thread, by name.
+    pub fn registers(&self) -> Option<&[(&'static str, u64)]> { self.registers.as_deref() }
+    /// Descriptor of the first `NT_PRSTATUS` note.
+    pub fn prstatus(&self) -> &[u8] { &self.prstatus }
+    /// Descriptor of the `NT_PRPSINFO` note.
+    pub fn prpsinfo(&self) -> &[u8] { &self.prpsinfo }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Decodes the process name, pid, signal and registers of a `CORE` file.
+    /// `None` for other object types or when neither note is present.
+    pub fn core_info(&self) -> Option<CoreInfo> {
+        if self.header.object_type() != ObjectFileType::CORE { return None }
+
+        let notes = self.notes();
+        let core_note = |note_type| {
+            notes.iter()
+                .find(|note| note.name() == "CORE" && note.note_type() == note_type)
+                .map(|note| note.desc().to_vec())
+        };
+        let prstatus = core_note(NT_PRSTATUS);
+        let prpsinfo = core_note(NT_PRPSINFO);
+        if prstatus.is_none() && prpsinfo.is_none() { return None }
+
+        let prstatus = prstatus.unwrap_or_default();
+        let prpsinfo = prpsinfo.unwrap_or_default();
+        let is_little = self.header.is_little();
+        let x86_64 = self.header.isa() == ISA::x86_64;
+        let text = |bytes: Option<&[u8]>| {
+            bytes.map(|bytes| {
+                let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(bytes.len());
+                String::from_utf8_lossy(&bytes[..end]).into_owned()
+            })
+        };
+
+        let (process_name, arguments, pid, signal, registers) =
+            if x86_64 {
+                let registers = prstatus.get(0x70..0x70 + 27 * 8).map(|bytes| {
+                    X86_64_REGISTERS.iter().zip(bytes.chunks(8)).map(|(&name, value)| {
+                        (name, make_u64(value, is_little))
+                    }).collect()
+                });
+                let pid = prpsinfo.get(0x18..0x1C).or_else(|| prstatus.get(0x20..0x24));
+
+                (
+                    text(prpsinfo.get(0x28..0x38)),
+                    text(prpsinfo.get(0x38..0x88)),
+                    pid.map(|bytes| make_u32(bytes, is_little)),
+                    prstatus.get(0x0C..0x0E).map(|bytes| make_u16(bytes, is_little)),
+                    registers,
+                )
+            } else {
+                (None, None, None, None, None)
+            };
+
+        Some(CoreInfo { process_name, arguments, pid, signal, registers, prstatus, prpsinfo })
+    }
+}
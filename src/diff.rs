@@ -0,0 +1,104 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, ObjectFileType, SectionHeader};
+use super::prelude::*;
+
+/// Differences between two parsed files, as produced by `ELF::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElfDiff<T> {
+    added_sections: Vec<String>,
+    removed_sections: Vec<String>,
+    resized_sections: Vec<(String, T, T)>,
+    entry_point: Option<(T, T)>,
+    object_type: Option<(ObjectFileType, ObjectFileType)>,
+    segment_count: Option<(usize, usize)>,
+}
+
+impl<T> ElfDiff<T>
+    where T: Copy
+{
+    pub fn added_sections(&self) -> &[String] { &self.added_sections }
+    pub fn removed_sections(&self) -> &[String] { &self.removed_sections }
+    /// Sections present in both files with a different size, as `(name, old, new)`.
+    pub fn resized_sections(&self) -> &[(String, T, T)] { &self.resized_sections }
+    pub fn entry_point(&self) -> Option<(T, T)> { self.entry_point }
+    pub fn object_type(&self) -> Option<(ObjectFileType, ObjectFileType)> { self.object_type }
+    pub fn segment_count(&self) -> Option<(usize, usize)> { self.segment_count }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_sections.is_empty()
+            && self.removed_sections.is_empty()
+            && self.resized_sections.is_empty()
+            && self.entry_point.is_none()
+            && self.object_type.is_none()
+            && self.segment_count.is_none()
+    }
+}
+
+impl<T> fmt::Display for ElfDiff<T>
+    where T: fmt::LowerHex + Copy
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() { return write!(f, "no differences") }
+
+        let mut lines = Vec::new();
+        if let Some((old, new)) = self.object_type {
+            lines.push(format!("type: {:?} -> {:?}", old, new));
+        }
+        if let Some((old, new)) = self.entry_point {
+            lines.push(format!("entry point: 0x{:x} -> 0x{:x}", old, new));
+        }
+        if let Some((old, new)) = self.segment_count {
+            lines.push(format!("segments: {} -> {}", old, new));
+        }
+        lines.extend(self.added_sections.iter().map(|name| format!("added section {}", name)));
+        lines.extend(self.removed_sections.iter().map(|name| format!("removed section {}", name)));
+        lines.extend(self.resized_sections.iter().map(|(name, old, new)| {
+            format!("resized section {}: 0x{:x} -> 0x{:x}", name, old, new)
+        }));
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + PartialEq,
+          D: AsRef<[u8]>
+{
+    /// Compares this file against `other`, treating `self` as the old version.
+    /// Sections are matched by name.
+    pub fn diff<E: AsRef<[u8]>>(&self, other: &ELF<T, E>) -> ElfDiff<T> {
+        let find = |sections: &[SectionHeader<T>], name: &str| {
+            sections.iter().find(|sh| sh.name() == name).map(|sh| sh.size())
+        };
+
+        let mut added_sections = Vec::new();
+        let mut resized_sections = Vec::new();
+        for sh in other.section_headers() {
+            match find(self.section_headers(), &sh.name()) {
+                None => added_sections.push(sh.name()),
+                Some(size) if size != sh.size() => resized_sections.push((sh.name(), size, sh.size())),
+                Some(_) => {}
+            }
+        }
+        let removed_sections = self.section_headers().iter()
+            .filter(|sh| find(other.section_headers(), &sh.name()).is_none())
+            .map(|sh| sh.name())
+            .collect();
+
+        ElfDiff {
+            added_sections,
+            removed_sections,
+            resized_sections,
+            entry_point: changed(self.header.entry_point(), other.header().entry_point()),
+            object_type: changed(self.header.object_type(), other.header().object_type()),
+            segment_count: changed(self.program_headers.len(), other.program_headers().len()),
+        }
+    }
+}
+
+fn changed<V: PartialEq>(old: V, new: V) -> Option<(V, V)> {
+    if old != new { Some((old, new)) } else { None }
+}
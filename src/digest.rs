@@ -0,0 +1,33 @@
+use core::fmt;
+
+use num::cast;
+use sha2::{Digest, Sha256};
+
+use super::ELF;
+use super::prelude::*;
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// SHA-256 of the `SHF_EXECINSTR` sections' contents, concatenated in
+    /// address order, so binaries differing only in metadata such as the
+    /// build ID hash the same.
+    ///
+    /// Sections at the same address, as in relocatable files, keep their
+    /// header order. Sections without file contents are skipped.
+    pub fn code_hash(&self) -> [u8; 32] {
+        let mut sections = self.section_headers.iter()
+            .filter(|sh| sh.is_executable())
+            .filter_map(|sh| Some((sh.target_addr(), sh.file_range(self.data().len())?)))
+            .collect::<Vec<_>>();
+        sections.sort_by_key(|(addr, _)| *addr);
+
+        let mut hasher = Sha256::new();
+        for (_, range) in sections {
+            hasher.update(&self.data()[range]);
+        }
+
+        hasher.finalize().into()
+    }
+}
@@ -0,0 +1,332 @@
+use core::fmt;
+use core::mem;
+use core::ops::Range;
+
+use num::cast;
+
+use super::header::Header;
+use super::program_header::{ProgramHeader, ProgramType};
+use super::section_header::{SectionHeader, SectionType};
+use super::{ELF, make_string, make_u32, make_u64};
+use super::prelude::*;
+
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicEntry<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    tag: DynamicTag,
+    value: T,
+}
+
+impl<T> DynamicEntry<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    fn location(program_headers: &[ProgramHeader<T>], section_headers: &[SectionHeader<T>]) -> Option<Range<usize>> {
+        let section = section_headers.iter().find(|sh| sh.section_type() == SectionType::Dynamic);
+        let segment = program_headers.iter().find(|ph| ph.program_type() == ProgramType::Dynamic);
+
+        match (section, segment) {
+            (Some(sh), _) => Some(sh.file_offset().as_()..sh.file_offset().as_().checked_add(sh.size().as_())?),
+            (None, Some(ph)) => Some(ph.offset().as_()..ph.offset().as_().checked_add(ph.file_size().as_())?),
+            (None, None) => None,
+        }
+    }
+
+    fn construct(
+        binary: &[u8],
+        header: &Header<T>,
+        location: Range<usize>,
+        entry_size: usize,
+        make_tag: impl Fn(&[u8], bool) -> u64,
+        make_unsigned: impl Fn(&[u8], bool) -> T,
+    ) -> Option<Vec<DynamicEntry<T>>> {
+        let word = entry_size / 2;
+        let mut entries = Vec::new();
+
+        for entry_point in location.step_by(entry_size) {
+            let tag = make_tag(binary.get(entry_point..entry_point + word)?, header.is_little());
+            let value = make_unsigned(binary.get(entry_point + word..entry_point + entry_size)?, header.is_little());
+            let tag = DynamicTag::new(tag);
+
+            entries.push(DynamicEntry { tag, value });
+            if tag == DynamicTag::Null { break }
+        }
+
+        Some(entries)
+    }
+
+    pub fn tag(&self) -> DynamicTag { self.tag }
+    pub fn value(&self) -> T { self.value }
+}
+
+impl DynamicEntry<u32> {
+    pub fn new(binary: &[u8], header: &Header<u32>, program_headers: &[ProgramHeader<u32>], section_headers: &[SectionHeader<u32>]) -> Option<Vec<DynamicEntry<u32>>> {
+        match Self::location(program_headers, section_headers) {
+            Some(location) => {
+                let make_tag = |bin: &[u8], is_little| make_u32(bin, is_little) as u64;
+                Self::construct(binary, header, location, 0x08, make_tag, make_u32)
+            }
+            None => Some(Vec::new()),
+        }
+    }
+}
+
+impl DynamicEntry<u64> {
+    pub fn new(binary: &[u8], header: &Header<u64>, program_headers: &[ProgramHeader<u64>], section_headers: &[SectionHeader<u64>]) -> Option<Vec<DynamicEntry<u64>>> {
+        match Self::location(program_headers, section_headers) {
+            Some(location) => Self::construct(binary, header, location, 0x10, make_u64, make_u64),
+            None => Some(Vec::new()),
+        }
+    }
+}
+
+/// The `DT_FLAGS` and `DT_FLAGS_1` words, which are 0 when the tag is absent.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DynFlags {
+    flags: u64,
+    flags_1: u64,
+}
+
+impl DynFlags {
+    pub fn raw_flags(&self) -> u64 { self.flags }
+    pub fn raw_flags_1(&self) -> u64 { self.flags_1 }
+
+    /// `DF_ORIGIN` or `DF_1_ORIGIN`: `$ORIGIN` is used in search paths.
+    pub fn origin(&self) -> bool { self.flags & 0x1 != 0 || self.flags_1 & 0x80 != 0 }
+    /// `DF_SYMBOLIC`: the object's own definitions are searched first.
+    pub fn symbolic(&self) -> bool { self.flags & 0x2 != 0 }
+    /// `DF_TEXTREL`: relocations modify read-only segments.
+    pub fn textrel(&self) -> bool { self.flags & 0x4 != 0 }
+    /// `DF_BIND_NOW`: all relocations are processed at load time.
+    pub fn bind_now(&self) -> bool { self.flags & 0x8 != 0 }
+    /// `DF_STATIC_TLS`: the object uses the static TLS model.
+    pub fn static_tls(&self) -> bool { self.flags & 0x10 != 0 }
+
+    /// `DF_1_NOW`, the `DT_FLAGS_1` counterpart of `DF_BIND_NOW`.
+    pub fn now(&self) -> bool { self.flags_1 & 0x1 != 0 }
+    /// `DF_1_GLOBAL`: symbols are made available to later loaded objects.
+    pub fn global(&self) -> bool { self.flags_1 & 0x2 != 0 }
+    /// `DF_1_GROUP`: symbols are only resolved within the object's group.
+    pub fn group(&self) -> bool { self.flags_1 & 0x4 != 0 }
+    /// `DF_1_NODELETE`: the object is never unloaded.
+    pub fn nodelete(&self) -> bool { self.flags_1 & 0x8 != 0 }
+    /// `DF_1_INITFIRST`: its initializers run before those of other objects.
+    pub fn initfirst(&self) -> bool { self.flags_1 & 0x20 != 0 }
+    /// `DF_1_NOOPEN`: the object can't be `dlopen`ed.
+    pub fn noopen(&self) -> bool { self.flags_1 & 0x40 != 0 }
+    /// `DF_1_INTERPOSE`: its symbols interpose those of everything but the executable.
+    pub fn interpose(&self) -> bool { self.flags_1 & 0x400 != 0 }
+    /// `DF_1_NODEFLIB`: the default library search paths are skipped.
+    pub fn nodeflib(&self) -> bool { self.flags_1 & 0x800 != 0 }
+    /// `DF_1_PIE`: the object is a position-independent executable.
+    pub fn pie(&self) -> bool { self.flags_1 & 0x0800_0000 != 0 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DynamicTag {
+    Null,
+    Needed,
+    PltRelSz,
+    PltGot,
+    Hash,
+    StrTab,
+    SymTab,
+    Rela,
+    RelaSz,
+    RelaEnt,
+    StrSz,
+    SymEnt,
+    Init,
+    Fini,
+    SoName,
+    RPath,
+    Symbolic,
+    Rel,
+    RelSz,
+    RelEnt,
+    PltRel,
+    Debug,
+    TextRel,
+    JmpRel,
+    BindNow,
+    InitArray,
+    FiniArray,
+    InitArraySz,
+    FiniArraySz,
+    RunPath,
+    Flags,
+    PreinitArray,
+    PreinitArraySz,
+    SymTabShndx,
+    RelrSz,
+    Relr,
+    RelrEnt,
+    GnuHash,
+    VerSym,
+    RelaCount,
+    RelCount,
+    Flags1,
+    VerDef,
+    VerDefNum,
+    VerNeed,
+    VerNeedNum,
+    Other(u64),
+}
+
+impl DynamicTag {
+    pub fn new(value: u64) -> DynamicTag {
+        match value {
+            0x0000_0000 => DynamicTag::Null,
+            0x0000_0001 => DynamicTag::Needed,
+            0x0000_0002 => DynamicTag::PltRelSz,
+            0x0000_0003 => DynamicTag::PltGot,
+            0x0000_0004 => DynamicTag::Hash,
+            0x0000_0005 => DynamicTag::StrTab,
+            0x0000_0006 => DynamicTag::SymTab,
+            0x0000_0007 => DynamicTag::Rela,
+            0x0000_0008 => DynamicTag::RelaSz,
+            0x0000_0009 => DynamicTag::RelaEnt,
+            0x0000_000A => DynamicTag::StrSz,
+            0x0000_000B => DynamicTag::SymEnt,
+            0x0000_000C => DynamicTag::Init,
+            0x0000_000D => DynamicTag::Fini,
+            0x0000_000E => DynamicTag::SoName,
+            0x0000_000F => DynamicTag::RPath,
+            0x0000_0010 => DynamicTag::Symbolic,
+            0x0000_0011 => DynamicTag::Rel,
+            0x0000_0012 => DynamicTag::RelSz,
+            0x0000_0013 => DynamicTag::RelEnt,
+            0x0000_0014 => DynamicTag::PltRel,
+            0x0000_0015 => DynamicTag::Debug,
+            0x0000_0016 => DynamicTag::TextRel,
+            0x0000_0017 => DynamicTag::JmpRel,
+            0x0000_0018 => DynamicTag::BindNow,
+            0x0000_0019 => DynamicTag::InitArray,
+            0x0000_001A => DynamicTag::FiniArray,
+            0x0000_001B => DynamicTag::InitArraySz,
+            0x0000_001C => DynamicTag::FiniArraySz,
+            0x0000_001D => DynamicTag::RunPath,
+            0x0000_001E => DynamicTag::Flags,
+            0x0000_0020 => DynamicTag::PreinitArray,
+            0x0000_0021 => DynamicTag::PreinitArraySz,
+            0x0000_0022 => DynamicTag::SymTabShndx,
+            0x0000_0023 => DynamicTag::RelrSz,
+            0x0000_0024 => DynamicTag::Relr,
+            0x0000_0025 => DynamicTag::RelrEnt,
+            0x6FFF_FEF5 => DynamicTag::GnuHash,
+            0x6FFF_FFF0 => DynamicTag::VerSym,
+            0x6FFF_FFF9 => DynamicTag::RelaCount,
+            0x6FFF_FFFA => DynamicTag::RelCount,
+            0x6FFF_FFFB => DynamicTag::Flags1,
+            0x6FFF_FFFC => DynamicTag::VerDef,
+            0x6FFF_FFFD => DynamicTag::VerDefNum,
+            0x6FFF_FFFE => DynamicTag::VerNeed,
+            0x6FFF_FFFF => DynamicTag::VerNeedNum,
+            _           => DynamicTag::Other(value),
+        }
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Returns the value of the first `.dynamic` entry tagged `tag`.
+    pub fn dynamic_value(&self, tag: DynamicTag) -> Option<T> {
+        self.dynamic.iter().find(|entry| entry.tag() == tag).map(|entry| entry.value())
+    }
+
+    /// Decodes `DT_FLAGS` and `DT_FLAGS_1`.
+    ///
+    /// `pie` is the linker's own record of a PIE, unlike the segment-based
+    /// guess of `pie_status`, but older toolchains don't set it.
+    pub fn dynamic_flags(&self) -> DynFlags {
+        let word = |tag| self.dynamic_value(tag).map_or(0, |value: T| value.as_() as u64);
+
+        DynFlags {
+            flags: word(DynamicTag::Flags),
+            flags_1: word(DynamicTag::Flags1),
+        }
+    }
+
+    /// Returns the `DT_NEEDED` libraries in the order the loader searches them.
+    /// Empty for static binaries.
+    pub fn needed_libraries(&self) -> Vec<String> {
+        self.dynamic.iter()
+            .filter(|entry| entry.tag() == DynamicTag::Needed)
+            .filter_map(|entry| self.dynamic_string(entry.value().as_()))
+            .collect()
+    }
+
+    /// Returns the `DT_SONAME` of a shared object, or `None` if it has none.
+    pub fn soname(&self) -> Option<String> {
+        self.dynamic_string(self.dynamic_value(DynamicTag::SoName)?.as_())
+    }
+
+    /// Returns the `DT_RPATH` search paths split on `:`, with `$ORIGIN` and
+    /// friends left unexpanded. `None` when the tag is absent.
+    pub fn rpath(&self) -> Option<Vec<String>> {
+        self.search_paths(DynamicTag::RPath)
+    }
+
+    /// Returns the `DT_RUNPATH` search paths, like `rpath`.
+    pub fn runpath(&self) -> Option<Vec<String>> {
+        self.search_paths(DynamicTag::RunPath)
+    }
+
+    fn search_paths(&self, tag: DynamicTag) -> Option<Vec<String>> {
+        let paths = self.dynamic_string(self.dynamic_value(tag)?.as_())?;
+        Some(paths.split(':').map(String::from).collect())
+    }
+
+    /// Reads the string at `offset` in the string table linked from `.dynamic`.
+    pub fn dynamic_string(&self, offset: usize) -> Option<String> {
+        let dynamic = self.section_headers.iter().find(|sh| sh.section_type() == SectionType::Dynamic)?;
+        let strtab = self.section_headers.get(dynamic.link() as usize)?;
+
+        make_string(self.data(), strtab.file_offset().as_().checked_add(offset)?)
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// Returns the initialization functions in the order the loader calls
+    /// them: `DT_INIT` followed by the `.init_array` entries.
+    ///
+    /// Addresses are returned as stored, without the load bias or any
+    /// relocation applied.
+    pub fn init_functions(&self) -> Vec<T> {
+        let mut functions = self.dynamic_value(DynamicTag::Init).into_iter().collect::<Vec<_>>();
+        functions.extend(self.function_array(SectionType::InitArray));
+
+        functions
+    }
+
+    /// Returns the termination functions in the order the loader calls
+    /// them: the `.fini_array` entries in reverse, then `DT_FINI`.
+    pub fn fini_functions(&self) -> Vec<T> {
+        let mut functions = self.function_array(SectionType::FiniArray);
+        functions.reverse();
+        functions.extend(self.dynamic_value(DynamicTag::Fini));
+
+        functions
+    }
+
+    fn function_array(&self, section_type: SectionType) -> Vec<T> {
+        let is_little = self.header.is_little();
+        let word_size = mem::size_of::<T>();
+
+        self.section_headers
+            .iter()
+            .filter(|sh| sh.section_type() == section_type)
+            .filter_map(|sh| self.data().get(sh.file_range(self.data().len())?))
+            .flat_map(|bytes| bytes.chunks_exact(word_size))
+            .filter_map(|word| {
+                if word_size == 4 { num::cast(make_u32(word, is_little)) }
+                else { num::cast(make_u64(word, is_little)) }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,69 @@
+use core::fmt;
+
+use super::Class;
+use super::prelude::*;
+
+/// Reasons `ELF::parse` and `parse_file` can reject their input, or
+/// `ELF::flatten` its segments.
+#[derive(Debug)]
+pub enum ElfError {
+    /// Reading `needed` bytes at `offset` ran past the end of the `len`-byte input.
+    Truncated { needed: usize, offset: usize, len: usize },
+    /// The input doesn't start with `\x7fELF`.
+    InvalidMagic,
+    /// The ELF class (32/64-bit) doesn't match the requested width; `found`
+    /// is the raw `EI_CLASS` byte.
+    ClassMismatch { expected: Class, found: u8 },
+    InvalidHeader,
+    InvalidProgramHeaders,
+    InvalidSectionHeaders,
+    InvalidSymbols,
+    /// Two `PT_LOAD` segments claim the same addresses.
+    OverlappingSegments,
+    /// The load base lies above the lowest `PT_LOAD` address.
+    InvalidLoadBase,
+    /// The memory image is too large to allocate.
+    ImageTooLarge,
+    /// Reading the file failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ElfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ElfError::Truncated { needed, offset, len } =>
+                write!(f, "truncated: need {} bytes at offset 0x{:x}, file is 0x{:x} bytes", needed, offset, len),
+            ElfError::InvalidMagic => write!(f, "not an ELF file: missing the \\x7fELF magic"),
+            ElfError::ClassMismatch { expected, found } => {
+                let found = match found {
+                    1 => "ELF32".to_string(),
+                    2 => "ELF64".to_string(),
+                    other => format!("EI_CLASS {}", other),
+                };
+                write!(f, "class mismatch: parsing as {:?} but the file is {}", expected, found)
+            }
+            ElfError::InvalidHeader => write!(f, "invalid ELF header: unknown data encoding, version or object type"),
+            ElfError::InvalidProgramHeaders => write!(f, "invalid program headers: a segment type is unknown"),
+            ElfError::InvalidSectionHeaders =>
+                write!(f, "invalid section headers: a section type is unknown or a section name can't be read"),
+            ElfError::InvalidSymbols =>
+                write!(f, "invalid symbol table: its string table is missing or a name can't be read"),
+            ElfError::OverlappingSegments => write!(f, "PT_LOAD segments overlap in memory"),
+            ElfError::InvalidLoadBase => write!(f, "load base is above the lowest PT_LOAD address"),
+            ElfError::ImageTooLarge => write!(f, "memory image is too large to allocate"),
+            #[cfg(feature = "std")]
+            ElfError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl core::error::Error for ElfError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            ElfError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
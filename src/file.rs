@@ -0,0 +1,93 @@
+use core::convert::TryFrom;
+
+#[cfg(feature = "std")]
+use std::{fs, path::Path};
+
+use super::{Class, ELF, get_elf_class};
+#[cfg(feature = "std")]
+use super::{ElfError, ElfInfo, ParseMode};
+use super::prelude::*;
+
+/// An ELF file of either class, for code that shouldn't have to pick
+/// between `ELF<u32>` and `ELF<u64>` up front.
+pub enum ElfFile<D = Vec<u8>> {
+    Elf32(ELF<u32, D>),
+    Elf64(ELF<u64, D>),
+}
+
+impl ElfFile {
+    /// Parses `binary` as 32- or 64-bit according to its `EI_CLASS` byte.
+    pub fn new(binary: Vec<u8>) -> Option<ElfFile> {
+        match get_elf_class(&binary)? {
+            Class::ELF32 => ELF::<u32>::new(binary).map(ElfFile::Elf32),
+            Class::ELF64 => ELF::<u64>::new(binary).map(ElfFile::Elf64),
+        }
+    }
+}
+
+/// Reads and parses the file at `path`, hiding its class behind `ElfInfo`.
+#[cfg(feature = "std")]
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Box<dyn ElfInfo>, ElfError> {
+    let binary = fs::read(path).map_err(ElfError::Io)?;
+
+    // anything but a 32-bit class goes through the 64-bit parser, which reports what is wrong
+    match get_elf_class(&binary) {
+        Some(Class::ELF32) => Ok(Box::new(ELF::<u32>::from_vec(binary, ParseMode::Strict)?)),
+        _ => Ok(Box::new(ELF::<u64>::from_vec(binary, ParseMode::Strict)?)),
+    }
+}
+
+impl<D> ElfFile<D> {
+    pub fn class(&self) -> Class {
+        match self {
+            ElfFile::Elf32(_) => Class::ELF32,
+            ElfFile::Elf64(_) => Class::ELF64,
+        }
+    }
+
+    pub fn as_elf32(&self) -> Option<&ELF<u32, D>> {
+        match self {
+            ElfFile::Elf32(elf) => Some(elf),
+            ElfFile::Elf64(_) => None,
+        }
+    }
+
+    pub fn as_elf64(&self) -> Option<&ELF<u64, D>> {
+        match self {
+            ElfFile::Elf32(_) => None,
+            ElfFile::Elf64(elf) => Some(elf),
+        }
+    }
+}
+
+impl<D> From<ELF<u32, D>> for ElfFile<D> {
+    fn from(elf: ELF<u32, D>) -> ElfFile<D> { ElfFile::Elf32(elf) }
+}
+
+impl<D> From<ELF<u64, D>> for ElfFile<D> {
+    fn from(elf: ELF<u64, D>) -> ElfFile<D> { ElfFile::Elf64(elf) }
+}
+
+impl<D> TryFrom<ElfFile<D>> for ELF<u32, D> {
+    type Error = ElfFile<D>;
+
+    /// Hands the file back unchanged when it is 64-bit.
+    fn try_from(file: ElfFile<D>) -> Result<ELF<u32, D>, ElfFile<D>> {
+        match file {
+            ElfFile::Elf32(elf) => Ok(elf),
+            other => Err(other),
+        }
+    }
+}
+
+impl<D> TryFrom<ElfFile<D>> for ELF<u64, D> {
+    type Error = ElfFile<D>;
+
+    /// Hands the file back unchanged when it is 32-bit.
+    fn try_from(file: ElfFile<D>) -> Result<ELF<u64, D>, ElfFile<D>> {
+        match file {
+            ElfFile::Elf64(elf) => Ok(elf),
+            other => Err(other),
+        }
+    }
+}
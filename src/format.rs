@@ -0,0 +1,41 @@
+use core::mem;
+
+/// How many hex digits addresses, offsets and sizes are padded to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Width {
+    /// 8 digits for ELF32, 16 for ELF64.
+    Auto,
+    Narrow,
+    Wide,
+    /// No padding at all.
+    Compact,
+}
+
+impl Width {
+    pub(crate) fn digits<T>(&self) -> usize {
+        match self {
+            Width::Auto    => mem::size_of::<T>() * 2,
+            Width::Narrow  => 8,
+            Width::Wide    => 16,
+            Width::Compact => 0,
+        }
+    }
+}
+
+/// Options for the `fmt_with` methods of program and section headers.
+/// The default matches their `Display` output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormatOptions {
+    pub hex_width: Width,
+    /// Spell flags out (`WRITE, ALLOC`) instead of using `readelf` letters (`WA`).
+    pub show_flags_long: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            hex_width: Width::Auto,
+            show_flags_long: false,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, SectionHeader, SectionType, SymbolType};
+use super::make_u32;
+use super::prelude::*;
+
+/// An `SHT_GROUP` section: sections that a linker keeps or discards together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionGroup {
+    name: String,
+    signature: String,
+    flags: u32,
+    members: Vec<usize>,
+    member_names: Vec<String>,
+}
+
+impl SectionGroup {
+    /// The name of the group section itself, usually `.group`.
+    pub fn name(&self) -> String { self.name.clone() }
+    /// The name of the symbol that identifies the group, e.g. a C++ inline function.
+    pub fn signature(&self) -> String { self.signature.clone() }
+    pub fn flags(&self) -> u32 { self.flags }
+    /// Whether `GRP_COMDAT` is set, i.e. duplicate groups with the same
+    /// signature are discarded at link time.
+    pub fn is_comdat(&self) -> bool { self.flags & 0x1 != 0 }
+    /// Section header indices of the members.
+    pub fn members(&self) -> &[usize] { &self.members }
+    /// Names of the members, in the same order as `members`.
+    pub fn member_names(&self) -> &[String] { &self.member_names }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Decodes every `SHT_GROUP` section. Groups whose contents lie outside
+    /// the file or whose signature symbol can't be found are skipped.
+    pub fn section_groups(&self) -> Vec<SectionGroup> {
+        self.section_headers.iter()
+            .filter(|sh| sh.section_type() == SectionType::Group)
+            .filter_map(|sh| self.section_group(sh))
+            .collect()
+    }
+
+    fn section_group(&self, section: &SectionHeader<T>) -> Option<SectionGroup> {
+        let is_little = self.header.is_little();
+        let bytes = self.data().get(section.file_range(self.data().len())?)?;
+        let mut words = bytes.chunks_exact(4).map(|word| make_u32(word, is_little));
+        let flags = words.next()?;
+        let members = words.map(|index| index as usize).collect::<Vec<_>>();
+        let member_names = members.iter()
+            .map(|&index| self.section(index).map(|sh| sh.name()).unwrap_or_default())
+            .collect();
+
+        // `sh_link` names the symbol table and `sh_info` the signature's index in it
+        let symbols = match section.linked_section(self)?.section_type() {
+            SectionType::SymTab => &self.symbols,
+            SectionType::DynSym => &self.dynamic_symbols,
+            _ => return None,
+        };
+        let symbol = symbols.get(section.info() as usize)?;
+        let signature = match symbol.sym_type() {
+            SymbolType::Section if symbol.name_str().is_empty() => self.section(symbol.section_index()?)?.name(),
+            _ => symbol.name(),
+        };
+
+        Some(SectionGroup {
+            name: section.name(),
+            signature,
+            flags,
+            members,
+            member_names,
+        })
+    }
+}
@@ -0,0 +1,207 @@
+use core::fmt;
+use core::mem;
+
+use num::cast;
+
+use super::{DynamicTag, ELF, ISA, ObjectFileType, ProgramHeader, ProgramType, SegmentFlags};
+use super::make_u32;
+use super::prelude::*;
+
+/// Relocation read-only status, as reported by `checksec`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relro {
+    /// No `PT_GNU_RELRO` segment.
+    None,
+    /// A `PT_GNU_RELRO` segment exists, but lazy binding leaves `.got.plt` writable.
+    Partial,
+    /// A `PT_GNU_RELRO` segment exists and binding is immediate, through
+    /// `DT_BIND_NOW`, `DF_BIND_NOW` in `DT_FLAGS` or `DF_1_NOW` in `DT_FLAGS_1`.
+    Full,
+}
+
+/// Position independence of an executable, as reported by `checksec`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PieStatus {
+    /// Neither `PT_INTERP` nor `PT_DYNAMIC` is present.
+    Static,
+    /// An `EXEC` object, loaded at the fixed addresses it was linked at.
+    NoPie,
+    /// A `DYN` object that can be loaded anywhere.
+    Pie,
+}
+
+/// How an executable gets its library code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Linkage {
+    /// Linked at fixed addresses with no dynamic loader involved.
+    StaticNoPie,
+    /// A self-relocating `DYN` object: it has `PT_DYNAMIC` for its own
+    /// relocations but neither `PT_INTERP` nor `DT_NEEDED` libraries.
+    StaticPie,
+    /// Loaded through `PT_INTERP`, or a shared library with `DT_NEEDED` entries.
+    Dynamic,
+}
+
+/// Control-flow integrity features from the `NT_GNU_PROPERTY_TYPE_0` note.
+///
+/// Only the flags of the binary's own architecture can be set.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CetInfo {
+    /// x86 indirect branch tracking (`GNU_PROPERTY_X86_FEATURE_1_IBT`).
+    pub ibt: bool,
+    /// x86 shadow stack (`GNU_PROPERTY_X86_FEATURE_1_SHSTK`).
+    pub shstk: bool,
+    /// AArch64 branch target identification (`GNU_PROPERTY_AARCH64_FEATURE_1_BTI`).
+    pub bti: bool,
+    /// AArch64 pointer authentication (`GNU_PROPERTY_AARCH64_FEATURE_1_PAC`).
+    pub pac: bool,
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy,
+          D: AsRef<[u8]>
+{
+    /// Reports whether the `PT_GNU_STACK` segment requests an executable stack.
+    ///
+    /// `None` means there is no such segment, which old toolchains and loaders
+    /// treat as an executable stack.
+    pub fn has_executable_stack(&self) -> Option<bool> {
+        self.program_headers
+            .iter()
+            .find(|ph| ph.program_type() == ProgramType::GnuStack)
+            .map(|ph| ph.flags() & 0b001 != 0)
+    }
+
+    /// Classifies the binary as static, non-PIE or PIE.
+    ///
+    /// `DYN` objects without `PT_INTERP` (shared libraries and static-pie
+    /// executables) are still position independent and report `Pie`.
+    /// Other object types, such as relocatable files, report `NoPie`.
+    pub fn pie_status(&self) -> PieStatus {
+        let (has_interp, has_dynamic) = self.program_headers.iter().fold((false, false), |(interp, dynamic), ph| {
+            match ph.program_type() {
+                ProgramType::Interp => (true, dynamic),
+                ProgramType::Dynamic => (interp, true),
+                _ => (interp, dynamic),
+            }
+        });
+
+        match self.header.object_type() {
+            ObjectFileType::EXEC | ObjectFileType::DYN if !has_interp && !has_dynamic => PieStatus::Static,
+            ObjectFileType::DYN => PieStatus::Pie,
+            _ => PieStatus::NoPie,
+        }
+    }
+
+    /// Lists `PT_LOAD` segments mapped both writable and executable.
+    pub fn rwx_segments(&self) -> Vec<&ProgramHeader<T>> {
+        self.program_headers
+            .iter()
+            .filter(|ph| {
+                let flags = SegmentFlags::new(ph.flags());
+                ph.program_type() == ProgramType::Load && flags.writable() && flags.executable()
+            })
+            .collect()
+    }
+
+    /// Reports whether any `PT_LOAD` segment is writable and executable.
+    pub fn has_rwx(&self) -> bool {
+        !self.rwx_segments().is_empty()
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    pub fn relro(&self) -> Relro {
+        let has_relro = self.program_headers.iter().any(|ph| ph.program_type() == ProgramType::GnuRelro);
+        if !has_relro { return Relro::None }
+
+        let flags = self.dynamic_flags();
+        let bind_now = self.dynamic_value(DynamicTag::BindNow).is_some() || flags.bind_now() || flags.now();
+
+        if bind_now { Relro::Full } else { Relro::Partial }
+    }
+
+    /// Whether the loader has to patch read-only segments, as flagged by
+    /// `DT_TEXTREL` or `DF_TEXTREL`. `false` for files without `.dynamic`.
+    pub fn has_text_relocations(&self) -> bool {
+        self.dynamic_value(DynamicTag::TextRel).is_some() || self.dynamic_flags().textrel()
+    }
+
+    /// Decodes the x86 or AArch64 `FEATURE_1_AND` property of the `GNU`
+    /// property note. Everything is `false` when the note or property is missing.
+    pub fn cet_properties(&self) -> CetInfo {
+        let feature_type = match self.header.isa() {
+            ISA::x86 | ISA::x86_64 => 0xC000_0002,
+            ISA::AArch64 => 0xC000_0000,
+            _ => return CetInfo::default(),
+        };
+
+        let features = self.notes().into_iter()
+            .filter(|note| note.name() == "GNU" && note.note_type() == 5)
+            .find_map(|note| find_property(note.desc(), feature_type, mem::size_of::<T>(), self.header.is_little()))
+            .unwrap_or(0);
+
+        match self.header.isa() {
+            ISA::AArch64 => CetInfo { bti: features & 0x1 != 0, pac: features & 0x2 != 0, ..CetInfo::default() },
+            _ => CetInfo { ibt: features & 0x1 != 0, shstk: features & 0x2 != 0, ..CetInfo::default() },
+        }
+    }
+
+    /// Tells static, static-PIE and dynamically linked binaries apart.
+    ///
+    /// Unlike `pie_status`, a `DYN` object without `PT_INTERP` is only
+    /// `StaticPie` when it needs no libraries; relocatable files report `StaticNoPie`.
+    pub fn linkage(&self) -> Linkage {
+        let has = |program_type| self.program_headers.iter().any(|ph| ph.program_type() == program_type);
+        let needs_libraries = self.dynamic_value(DynamicTag::Needed).is_some();
+
+        if has(ProgramType::Interp) { return Linkage::Dynamic }
+
+        match self.header.object_type() {
+            ObjectFileType::DYN if has(ProgramType::Dynamic) && !needs_libraries => Linkage::StaticPie,
+            ObjectFileType::DYN if has(ProgramType::Dynamic) => Linkage::Dynamic,
+            _ => Linkage::StaticNoPie,
+        }
+    }
+
+    /// Reports whether `__stack_chk_fail` appears in `.dynsym` or `.symtab`.
+    pub fn has_stack_canary(&self) -> bool {
+        self.symbols.iter()
+            .chain(self.dynamic_symbols.iter())
+            .any(|sym| unversioned(&sym.name()) == "__stack_chk_fail")
+    }
+
+    /// Reports whether any fortified libc function (`__memcpy_chk` and
+    /// friends, matched by the `_chk` suffix) is imported.
+    pub fn uses_fortify(&self) -> bool {
+        self.symbols.iter()
+            .chain(self.dynamic_symbols.iter())
+            .any(|sym| sym.shndx() == 0 && unversioned(&sym.name()).ends_with("_chk"))
+    }
+}
+
+// properties are (type, size, data) triples with data padded to the word size
+fn find_property(desc: &[u8], property_type: u32, align: usize, is_little: bool) -> Option<u32> {
+    let mut offset = 0;
+
+    while let Some(fields) = desc.get(offset..offset + 0x08) {
+        let pr_type = make_u32(&fields[0x00..0x04], is_little);
+        let size = make_u32(&fields[0x04..0x08], is_little) as usize;
+        let data = desc.get(offset + 0x08..(offset + 0x08).checked_add(size)?)?;
+
+        if pr_type == property_type {
+            return Some(make_u32(data.get(0x00..0x04)?, is_little));
+        }
+        offset = (offset + 0x08 + size).next_multiple_of(align);
+    }
+
+    None
+}
+
+// `.symtab` names of versioned imports carry a suffix such as `@GLIBC_2.4`
+fn unversioned(name: &str) -> &str {
+    name.split('@').next().unwrap_or(name)
+}
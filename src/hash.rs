@@ -0,0 +1,112 @@
+use core::fmt;
+use core::mem;
+
+use num::cast;
+
+use super::{ELF, SectionHeader, SectionType, Symbol};
+use super::{make_u32, make_u64};
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Looks `name` up in `.dynsym` through `.gnu.hash` or `.hash` when either
+    /// exists, and by scanning every dynamic symbol otherwise.
+    pub fn lookup_dynamic_symbol(&self, name: &str) -> Option<Symbol<T>> {
+        let gnu_hash = self.section_headers.iter().find(|sh| sh.section_type() == SectionType::GnuHash);
+        let sysv_hash = self.section_headers.iter().find(|sh| sh.section_type() == SectionType::Hash);
+
+        let index = match (gnu_hash, sysv_hash) {
+            (Some(section), _) => self.gnu_hash_lookup(section, name),
+            (None, Some(section)) => self.sysv_hash_lookup(section, name),
+            (None, None) => self.dynamic_symbols.iter().position(|sym| sym.name() == name),
+        }?;
+
+        self.dynamic_symbols.get(index).cloned()
+    }
+
+    fn sysv_hash_lookup(&self, section: &SectionHeader<T>, name: &str) -> Option<usize> {
+        let is_little = self.header.is_little();
+        let start = section.file_offset().as_();
+        let word = |index: usize| {
+            let offset = index.checked_mul(4)?.checked_add(start)?;
+            self.data().get(offset..offset.checked_add(4)?).map(|bytes| make_u32(bytes, is_little) as usize)
+        };
+
+        let bucket_num = word(0)?;
+        let chain_num = word(1)?;
+        if bucket_num == 0 { return None }
+
+        let hash = sysv_hash(name) as usize;
+        let mut index = word((hash % bucket_num).checked_add(2)?)?;
+
+        // every chain visits each symbol at most once, so bound the walk by nchain
+        for _ in 0..chain_num {
+            if index == 0 { return None }
+            if self.dynamic_symbols.get(index)?.name() == name { return Some(index) }
+
+            index = word(bucket_num.checked_add(index)?.checked_add(2)?)?;
+        }
+
+        None
+    }
+
+    fn gnu_hash_lookup(&self, section: &SectionHeader<T>, name: &str) -> Option<usize> {
+        let is_little = self.header.is_little();
+        let start = section.file_offset().as_();
+        let word_size = mem::size_of::<T>();
+        let word_bits = word_size * 8;
+        let read_u32 = |offset: usize| {
+            self.data().get(offset..offset.checked_add(4)?).map(|bytes| make_u32(bytes, is_little) as usize)
+        };
+        let read_word = |offset: usize| {
+            let bytes = self.data().get(offset..offset.checked_add(word_size)?)?;
+            if word_size == 4 { Some(make_u32(bytes, is_little) as u64) }
+            else { Some(make_u64(bytes, is_little)) }
+        };
+
+        let bucket_num = read_u32(start)?;
+        let symbol_offset = read_u32(start.checked_add(0x4)?)?;
+        let bloom_size = read_u32(start.checked_add(0x8)?)?;
+        let bloom_shift = read_u32(start.checked_add(0xC)?)?;
+        if bucket_num == 0 || bloom_size == 0 { return None }
+
+        let bloom_start = start.checked_add(0x10)?;
+        let buckets_start = bloom_size.checked_mul(word_size)?.checked_add(bloom_start)?;
+        let chain_start = bucket_num.checked_mul(4)?.checked_add(buckets_start)?;
+
+        let hash = gnu_hash(name) as usize;
+        let bloom = read_word(bloom_start.checked_add(hash / word_bits % bloom_size * word_size)?)?;
+        // a shift as wide as the hash or wider is corrupt, not an empty filter
+        let shifted = hash.checked_shr(bloom_shift as u32)?;
+        let mask = (1u64 << (hash % word_bits)) | (1u64 << (shifted % word_bits));
+        if bloom & mask != mask { return None }
+
+        let mut index = read_u32(buckets_start.checked_add(hash % bucket_num * 4)?)?;
+        if index < symbol_offset { return None }
+
+        loop {
+            let chain_hash = read_u32((index - symbol_offset).checked_mul(4)?.checked_add(chain_start)?)?;
+            if (hash | 1) == (chain_hash | 1) && self.dynamic_symbols.get(index)?.name() == name {
+                return Some(index);
+            }
+            if chain_hash & 1 != 0 { return None }
+
+            index = index.checked_add(1)?;
+        }
+    }
+}
+
+fn sysv_hash(name: &str) -> u32 {
+    name.bytes().fold(0u32, |hash, byte| {
+        let hash = (hash << 4).wrapping_add(byte as u32);
+        let high = hash & 0xF000_0000;
+        (hash ^ (high >> 24)) & !high
+    })
+}
+
+fn gnu_hash(name: &str) -> u32 {
+    name.bytes().fold(5381u32, |hash, byte| {
+        hash.wrapping_mul(33).wrapping_add(byte as u32)
+    })
+}
@@ -1,6 +1,12 @@
-use std::fmt;
-use super::{make_u16, make_u32, make_u64};
+use core::fmt;
 
+use num::cast;
+
+use super::{ElfError, Reader};
+use super::{split_u16, split_u32, split_u64};
+use super::prelude::*;
+
+#[derive(PartialEq)]
 pub struct Header<T>
 {
     elf_class: Class,
@@ -28,20 +34,125 @@ impl<T> Header<T>
         self.endian == Endian::Little
     }
 
+    pub fn endian(&self) -> Endian { self.endian }
+
+    pub fn header_size(&self) -> u16 { self.header_size }
     pub fn ph_size(&self) -> u16 { self.program_header_size }
     pub fn ph_num(&self) -> u16 { self.program_header_number }
     pub fn sh_size(&self) -> u16 { self.section_header_size }
     pub fn sh_num(&self) -> u16 { self.section_header_number }
     pub fn shstrndx(&self) -> u16 { self.section_name_table_entry }
 
+    /// `EI_ABIVERSION`, whose meaning depends on the OS/ABI.
+    pub fn abi_version(&self) -> u8 { self.abi_version }
     pub fn entry_point(&self) -> T { self.entry_point }
+    pub fn object_type(&self) -> ObjectFileType { self.object_type }
+    pub fn isa(&self) -> ISA { self.target_isa }
+    pub fn flags(&self) -> u32 { self.flags }
+
+    /// Decodes the well-known `e_flags` bits of ARM, MIPS and RISC-V objects
+    /// into the names `readelf -h` prints, e.g. `["Version5 EABI", "hard-float ABI"]`.
+    /// Other machines get the raw value in hex.
+    pub fn decoded_flags(&self) -> Vec<String> {
+        decode_flags(self.target_isa, self.flags).unwrap_or_else(|| vec![format!("0x{:x}", self.flags)])
+    }
+}
+
+fn decode_flags(isa: ISA, flags: u32) -> Option<Vec<String>> {
+    // names of the single-bit flags in `pairs` that are set
+    let set = |pairs: &[(u32, &str)]| {
+        pairs.iter().filter(|(mask, _)| flags & mask != 0).map(|(_, name)| name.to_string()).collect::<Vec<_>>()
+    };
+
+    let names = match isa {
+        ISA::ARM => {
+            let version = flags >> 24;
+            let mut names = vec![match version {
+                0 => "GNU EABI".to_string(),
+                1..=5 => format!("Version{} EABI", version),
+                _ => format!("<unrecognized EABI version {}>", version),
+            }];
+
+            if version == 5 {
+                names.extend(set(&[(0x0000_0200, "soft-float ABI"), (0x0000_0400, "hard-float ABI")]));
+            }
+            if version >= 4 {
+                names.extend(set(&[(0x0080_0000, "BE8"), (0x0040_0000, "LE8")]));
+            }
+
+            names
+        }
+        ISA::MIPS => {
+            let mut names = set(&[
+                (0x0000_0001, "noreorder"),
+                (0x0000_0002, "pic"),
+                (0x0000_0004, "cpic"),
+                (0x0000_0020, "abi2"),
+                (0x0000_0100, "32bitmode"),
+                (0x0000_0200, "fp64"),
+                (0x0000_0400, "nan2008"),
+            ]);
+
+            match flags & 0x0000_F000 {
+                0x1000 => names.push("o32".to_string()),
+                0x2000 => names.push("o64".to_string()),
+                0x3000 => names.push("eabi32".to_string()),
+                0x4000 => names.push("eabi64".to_string()),
+                _ => {}
+            }
+
+            let arch = match flags >> 28 {
+                0x0 => "mips1",
+                0x1 => "mips2",
+                0x2 => "mips3",
+                0x3 => "mips4",
+                0x4 => "mips5",
+                0x5 => "mips32",
+                0x6 => "mips64",
+                0x7 => "mips32r2",
+                0x8 => "mips64r2",
+                0x9 => "mips32r6",
+                0xA => "mips64r6",
+                _ => "unknown ISA",
+            };
+            names.push(arch.to_string());
+
+            names
+        }
+        ISA::RISC_V => {
+            let mut names = set(&[(0x0000_0001, "RVC"), (0x0000_0008, "RVE"), (0x0000_0010, "TSO")]);
+
+            let float_abi = match flags & 0x0000_0006 {
+                0x0 => "soft-float ABI",
+                0x2 => "single-float ABI",
+                0x4 => "double-float ABI",
+                _ => "quad-float ABI",
+            };
+            names.push(float_abi.to_string());
+
+            names
+        }
+        _ => return None,
+    };
+
+    Some(names)
 }
 
-pub fn get_elf_class(binary: &Vec<u8>) -> Option<Class> {
-    match binary[0x4] {
+/// Peeks at `EI_CLASS` so callers can choose between `ELF<u32>` and `ELF<u64>`.
+pub fn get_elf_class(binary: &[u8]) -> Option<Class> {
+    match binary.get(0x4)? {
         1 => Some(Class::ELF32),
         2 => Some(Class::ELF64),
-        _ => return None,
+        _ => None,
+    }
+}
+
+/// Peeks at `EI_DATA` without parsing the rest of the header.
+pub fn get_elf_endian(binary: &[u8]) -> Option<Endian> {
+    match binary.get(0x5)? {
+        1 => Some(Endian::Little),
+        2 => Some(Endian::Big),
+        _ => None,
     }
 }
 
@@ -52,70 +163,168 @@ impl<T> Header<T>
     pub fn sh_offset(&self) -> T { self.section_header_offset }
 }
 
+/// Widened copies of the class-sized fields, for code that isn't generic over `T`.
+impl<T> Header<T>
+    where T: Copy + cast::AsPrimitive<u64>
+{
+    pub fn entry_point_u64(&self) -> u64 { self.entry_point.as_() }
+    pub fn ph_offset_u64(&self) -> u64 { self.program_header_offset.as_() }
+    pub fn sh_offset_u64(&self) -> u64 { self.section_header_offset.as_() }
+}
+
+impl<T> Header<T>
+    where T: fmt::Display + fmt::LowerHex + Copy
+{
+    /// Formats the header the way GNU `readelf -h` prints it.
+    pub fn format_readelf(&self) -> String {
+        let class = match self.elf_class {
+            Class::ELF32 => "ELF32",
+            Class::ELF64 => "ELF64",
+        };
+        let data = match self.endian {
+            Endian::Little => "2's complement, little endian",
+            Endian::Big => "2's complement, big endian",
+        };
+
+        let ident = self.ident();
+        let magic = ident.iter().map(|b| format!("{:02x} ", b)).collect::<String>();
+
+        let lines = [
+            ("Magic:", magic),
+            ("Class:", class.to_string()),
+            ("Data:", data.to_string()),
+            ("Version:", "1 (current)".to_string()),
+            ("OS/ABI:", self.target_abi.readelf_name()),
+            ("ABI Version:", self.abi_version.to_string()),
+            ("Type:", self.object_type.readelf_name()),
+            ("Machine:", self.target_isa.readelf_name()),
+            ("Version:", "0x1".to_string()),
+            ("Entry point address:", format!("0x{:x}", self.entry_point)),
+            ("Start of program headers:", format!("{} (bytes into file)", self.program_header_offset)),
+            ("Start of section headers:", format!("{} (bytes into file)", self.section_header_offset)),
+            ("Flags:", self.readelf_flags()),
+            ("Size of this header:", format!("{} (bytes)", self.header_size)),
+            ("Size of program headers:", format!("{} (bytes)", self.program_header_size)),
+            ("Number of program headers:", self.program_header_number.to_string()),
+            ("Size of section headers:", format!("{} (bytes)", self.section_header_size)),
+            ("Number of section headers:", self.section_header_number.to_string()),
+            ("Section header string table index:", self.section_name_table_entry.to_string()),
+        ];
+
+        let body = lines.iter().map(|(label, value)| {
+            if *label == "Magic:" { format!("  {:<9}{}\n", label, value) }
+            else { format!("  {:<35}{}\n", label, value) }
+        }).collect::<String>();
+
+        format!("ELF Header:\n{}", body)
+    }
+
+    // readelf follows the raw value with the decoded names, if it knows any
+    fn readelf_flags(&self) -> String {
+        let raw = format!("0x{:x}", self.flags);
+        match decode_flags(self.target_isa, self.flags) {
+            Some(names) => [vec![raw], names].concat().join(", "),
+            None => raw,
+        }
+    }
+
+    fn ident(&self) -> [u8; 16] {
+        let mut ident = [0u8; 16];
+        ident[0x0..0x4].copy_from_slice(&[0x7F, b'E', b'L', b'F']);
+        ident[0x4] = match self.elf_class { Class::ELF32 => 1, Class::ELF64 => 2 };
+        ident[0x5] = match self.endian { Endian::Little => 1, Endian::Big => 2 };
+        ident[0x6] = 1;
+        ident[0x7] = self.target_abi.raw();
+        ident[0x8] = self.abi_version;
+
+        ident
+    }
+
+    fn serialize(&self, split_unsigned: impl Fn(T, bool) -> Vec<u8>) -> Vec<u8> {
+        let is_little = self.is_little();
+        let mut bytes = self.ident().to_vec();
+
+        bytes.extend(split_u16(self.object_type.raw(), is_little));
+        bytes.extend(split_u16(self.target_isa.raw(), is_little));
+        bytes.extend(split_u32(1, is_little));
+        bytes.extend(split_unsigned(self.entry_point, is_little));
+        bytes.extend(split_unsigned(self.program_header_offset, is_little));
+        bytes.extend(split_unsigned(self.section_header_offset, is_little));
+        bytes.extend(split_u32(self.flags, is_little));
+        bytes.extend(split_u16(self.header_size, is_little));
+        bytes.extend(split_u16(self.program_header_size, is_little));
+        bytes.extend(split_u16(self.program_header_number, is_little));
+        bytes.extend(split_u16(self.section_header_size, is_little));
+        bytes.extend(split_u16(self.section_header_number, is_little));
+        bytes.extend(split_u16(self.section_name_table_entry, is_little));
+
+        bytes
+    }
+}
+
 impl Header<u32> {
-    pub fn new (binary: &Vec<u8>) -> Option<Header<u32>> {
-        construct(binary, |bin, offset, is_little| {
-            let value = make_u32(&bin[offset..offset + 4], is_little);
-            (value, offset + 4)
-        })
+    pub fn new(binary: &[u8]) -> Option<Header<u32>> {
+        construct(binary, |reader| reader.u32())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.serialize(split_u32)
     }
 }
 
 impl Header<u64> {
-    pub fn new(binary: &Vec<u8>) -> Option<Header<u64>> {
-        construct(binary, |bin, offset, is_little| {
-            let value = make_u64(&bin[offset..offset + 8], is_little);
-            (value, offset + 8)
-        })
+    pub fn new(binary: &[u8]) -> Option<Header<u64>> {
+        construct(binary, |reader| reader.u64())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.serialize(split_u64)
     }
 }
 
-fn construct<T>(binary: &Vec<u8>, truncator: impl Fn(&Vec<u8>, usize, bool) -> (T, usize)) -> Option<Header<T>>
+fn construct<T>(binary: &[u8], read_unsigned: impl Fn(&mut Reader) -> Result<T, ElfError>) -> Option<Header<T>>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex
 {
-    let magic = make_u32(&binary[0x0..0x4], true);
+    // e_ident is made of single bytes, so its byte order doesn't matter
+    let mut ident = Reader::new(binary, true);
 
-    if magic != 0x46_4C_45_7F { return None }
-    if binary[0x6] != 1 { return None }
-    if binary[0x14] != 1 { return None }
+    if ident.bytes(4).ok()? != [0x7F, b'E', b'L', b'F'] { return None }
 
-    let elf_class = match binary[0x4] {
+    let elf_class = match ident.u8().ok()? {
         1 => Class::ELF32,
         2 => Class::ELF64,
         _ => return None,
     };
 
-    let endian = match binary[0x5] {
+    let endian = match ident.u8().ok()? {
         1 => Endian::Little,
         2 => Endian::Big,
         _ => return None,
     };
 
-    let is_little = endian == Endian::Little;
-
-    let target_abi = TargetABI::new(binary[0x7])?;
-    let abi_version = binary[0x8];
-    let object_type = ObjectFileType::new(make_u16(&binary[0x10..0x12], is_little))?;
-    let target_isa = ISA::new(make_u16(&binary[0x12..0x14], is_little))?;
-
-    let offset = 0x18;
-    let (entry_point, offset) = truncator(binary, offset, is_little);
-    let (program_header_offset, offset) = truncator(binary, offset, is_little);
-    let (section_header_offset, offset) = truncator(binary, offset, is_little);
-
-    let flags = make_u32(&binary[offset..offset + 4], is_little);
-    let offset = offset + 4;
-    let header_size = make_u16(&binary[offset..offset + 2], is_little);
-    let offset = offset + 2;
-    let program_header_size = make_u16(&binary[offset..offset + 2], is_little);
-    let offset = offset + 2;
-    let program_header_number = make_u16(&binary[offset..offset + 2], is_little);
-    let offset = offset + 2;
-    let section_header_size = make_u16(&binary[offset..offset + 2], is_little);
-    let offset = offset + 2;
-    let section_header_number = make_u16(&binary[offset..offset + 2], is_little);
-    let offset = offset + 2;
-    let section_name_table_entry = make_u16(&binary[offset..offset + 2], is_little);
+    if ident.u8().ok()? != 1 { return None }
+
+    let target_abi = TargetABI::new(ident.u8().ok()?);
+    let abi_version = ident.u8().ok()?;
+
+    let mut reader = Reader::new(binary, endian == Endian::Little);
+    reader.seek(0x10);
+
+    let object_type = ObjectFileType::new(reader.u16().ok()?)?;
+    let target_isa = ISA::new(reader.u16().ok()?);
+
+    if reader.u32().ok()? != 1 { return None }
+
+    let entry_point = read_unsigned(&mut reader).ok()?;
+    let program_header_offset = read_unsigned(&mut reader).ok()?;
+    let section_header_offset = read_unsigned(&mut reader).ok()?;
+    let flags = reader.u32().ok()?;
+    let header_size = reader.u16().ok()?;
+    let program_header_size = reader.u16().ok()?;
+    let program_header_number = reader.u16().ok()?;
+    let section_header_size = reader.u16().ok()?;
+    let section_header_number = reader.u16().ok()?;
+    let section_name_table_entry = reader.u16().ok()?;
 
     Some(Header {
         elf_class,
@@ -187,20 +396,31 @@ impl<T> fmt::Display for Header<T>
     }
 }
 
-#[derive(Debug)]
+impl<T> fmt::Debug for Header<T>
+    where T: fmt::LowerHex
+{
+    /// One line, e.g. `ELF64 DYN x86_64 entry=0x1040`; use `Display` for every field.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {:?} {:?} entry=0x{:x}", self.elf_class, self.object_type, self.target_isa, self.entry_point)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Class {
     ELF32,
     ELF64,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endian {
     Little,
     Big,
 }
 
-#[derive(Debug)]
-#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub enum TargetABI {
     SystemV,
     HP_UX,
@@ -212,18 +432,22 @@ pub enum TargetABI {
     IRIX,
     FreeBSD,
     Tru64,
-    NovellModestro,
+    NovellModesto,
     OpenBSD,
     OpenVMS,
     NonStopKernel,
     AROS,
     FenixOS,
     CloudABI,
+    ARM_AEABI,
+    ARM,
+    Standalone,
+    Unknown(u8),
 }
 
 impl TargetABI {
-    pub fn new(value: u8) -> Option<TargetABI> {
-        let abi = match value {
+    pub fn new(value: u8) -> TargetABI {
+        match value {
             0x00 => TargetABI::SystemV,
             0x01 => TargetABI::HP_UX,
             0x02 => TargetABI::NetBSD,
@@ -234,21 +458,75 @@ impl TargetABI {
             0x08 => TargetABI::IRIX,
             0x09 => TargetABI::FreeBSD,
             0x0A => TargetABI::Tru64,
-            0x0B => TargetABI::NovellModestro,
+            0x0B => TargetABI::NovellModesto,
             0x0C => TargetABI::OpenBSD,
             0x0D => TargetABI::OpenVMS,
             0x0E => TargetABI::NonStopKernel,
             0x0F => TargetABI::AROS,
             0x10 => TargetABI::FenixOS,
             0x11 => TargetABI::CloudABI,
-            _    => return None,
-        };
+            0x40 => TargetABI::ARM_AEABI,
+            0x61 => TargetABI::ARM,
+            0xFF => TargetABI::Standalone,
+            _    => TargetABI::Unknown(value),
+        }
+    }
 
-        Some(abi)
+    pub fn raw(&self) -> u8 {
+        match self {
+            TargetABI::SystemV        => 0x00,
+            TargetABI::HP_UX          => 0x01,
+            TargetABI::NetBSD         => 0x02,
+            TargetABI::Linux          => 0x03,
+            TargetABI::GNUHard        => 0x04,
+            TargetABI::Solaris        => 0x06,
+            TargetABI::AIX            => 0x07,
+            TargetABI::IRIX           => 0x08,
+            TargetABI::FreeBSD        => 0x09,
+            TargetABI::Tru64          => 0x0A,
+            TargetABI::NovellModesto  => 0x0B,
+            TargetABI::OpenBSD        => 0x0C,
+            TargetABI::OpenVMS        => 0x0D,
+            TargetABI::NonStopKernel  => 0x0E,
+            TargetABI::AROS           => 0x0F,
+            TargetABI::FenixOS        => 0x10,
+            TargetABI::CloudABI       => 0x11,
+            TargetABI::ARM_AEABI      => 0x40,
+            TargetABI::ARM            => 0x61,
+            TargetABI::Standalone     => 0xFF,
+            TargetABI::Unknown(value) => *value,
+        }
+    }
+
+    fn readelf_name(&self) -> String {
+        match self {
+            TargetABI::SystemV        => "UNIX - System V".to_string(),
+            TargetABI::HP_UX          => "UNIX - HP-UX".to_string(),
+            TargetABI::NetBSD         => "UNIX - NetBSD".to_string(),
+            TargetABI::Linux          => "UNIX - GNU".to_string(),
+            TargetABI::GNUHard        => "GNU/Hurd".to_string(),
+            TargetABI::Solaris        => "UNIX - Solaris".to_string(),
+            TargetABI::AIX            => "UNIX - AIX".to_string(),
+            TargetABI::IRIX           => "UNIX - IRIX".to_string(),
+            TargetABI::FreeBSD        => "UNIX - FreeBSD".to_string(),
+            TargetABI::Tru64          => "UNIX - TRU64".to_string(),
+            TargetABI::NovellModesto  => "Novell - Modesto".to_string(),
+            TargetABI::OpenBSD        => "UNIX - OpenBSD".to_string(),
+            TargetABI::OpenVMS        => "VMS - OpenVMS".to_string(),
+            TargetABI::NonStopKernel  => "HP - Non-Stop Kernel".to_string(),
+            TargetABI::AROS           => "AROS".to_string(),
+            TargetABI::FenixOS        => "FenixOS".to_string(),
+            TargetABI::CloudABI       => "Nuxi CloudABI".to_string(),
+            TargetABI::ARM_AEABI      => "ARM EABI".to_string(),
+            TargetABI::ARM            => "ARM".to_string(),
+            TargetABI::Standalone     => "Standalone App".to_string(),
+            TargetABI::Unknown(value) => format!("<unknown: {:x}>", value),
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ObjectFileType {
     NONE,
     REL,
@@ -278,43 +556,151 @@ impl ObjectFileType {
 
         Some(file_type)
     }
+
+    pub fn raw(&self) -> u16 {
+        match self {
+            ObjectFileType::NONE   => 0x0000,
+            ObjectFileType::REL    => 0x0001,
+            ObjectFileType::EXEC   => 0x0002,
+            ObjectFileType::DYN    => 0x0003,
+            ObjectFileType::CORE   => 0x0004,
+            ObjectFileType::LOOS   => 0xFE00,
+            ObjectFileType::HIOS   => 0xFEFF,
+            ObjectFileType::LOPROC => 0xFF00,
+            ObjectFileType::HIPROC => 0xFFFF,
+        }
+    }
+
+    fn readelf_name(&self) -> String {
+        match self {
+            ObjectFileType::NONE   => "NONE (None)".to_string(),
+            ObjectFileType::REL    => "REL (Relocatable file)".to_string(),
+            ObjectFileType::EXEC   => "EXEC (Executable file)".to_string(),
+            ObjectFileType::DYN    => "DYN (Shared object file)".to_string(),
+            ObjectFileType::CORE   => "CORE (Core file)".to_string(),
+            ObjectFileType::LOOS   => format!("OS Specific: ({:x})", 0xFE00),
+            ObjectFileType::HIOS   => format!("OS Specific: ({:x})", 0xFEFF),
+            ObjectFileType::LOPROC => format!("Processor Specific: ({:x})", 0xFF00),
+            ObjectFileType::HIPROC => format!("Processor Specific: ({:x})", 0xFFFF),
+        }
+    }
 }
 
-#[derive(Debug)]
-#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 pub enum ISA {
-    NONE,
-    SPARC,
-    x86,
-    MIPS,
-    PowerPC,
-    S390,
-    ARM,
-    SuperH,
-    IA_64,
-    x86_64,
-    AArch64,
-    RISC_V,
+    NONE,          // EM_NONE (0x00)
+    SPARC,         // EM_SPARC (0x02)
+    x86,           // EM_386 (0x03)
+    M68k,          // EM_68K (0x04)
+    MIPS,          // EM_MIPS (0x08)
+    PowerPC,       // EM_PPC (0x14)
+    PowerPC64,     // EM_PPC64 (0x15)
+    S390,          // EM_S390 (0x16)
+    ARM,           // EM_ARM (0x28)
+    SuperH,        // EM_SH (0x2A)
+    SPARCV9,       // EM_SPARCV9 (0x2B)
+    TriCore,       // EM_TRICORE (0x2C)
+    IA_64,         // EM_IA_64 (0x32)
+    x86_64,        // EM_X86_64 (0x3E)
+    AVR,           // EM_AVR (0x53)
+    Xtensa,        // EM_XTENSA (0x5E)
+    MSP430,        // EM_MSP430 (0x69)
+    Hexagon,       // EM_QDSP6 (0xA4)
+    AArch64,       // EM_AARCH64 (0xB7)
+    MicroBlaze,    // EM_MICROBLAZE (0xBD)
+    RISC_V,        // EM_RISCV (0xF3)
+    BPF,           // EM_BPF (0xF7)
+    LoongArch,     // EM_LOONGARCH (0x102)
+    Unknown(u16),
 }
 
 impl ISA {
-    pub fn new(value: u16) -> Option<ISA> {
-        let isa = match value {
+    pub fn new(value: u16) -> ISA {
+        match value {
             0x00 => ISA::NONE,
             0x02 => ISA::SPARC,
             0x03 => ISA::x86,
+            0x04 => ISA::M68k,
             0x08 => ISA::MIPS,
             0x14 => ISA::PowerPC,
+            0x15 => ISA::PowerPC64,
             0x16 => ISA::S390,
             0x28 => ISA::ARM,
             0x2A => ISA::SuperH,
+            0x2B => ISA::SPARCV9,
+            0x2C => ISA::TriCore,
             0x32 => ISA::IA_64,
             0x3E => ISA::x86_64,
+            0x53 => ISA::AVR,
+            0x5E => ISA::Xtensa,
+            0x69 => ISA::MSP430,
+            0xA4 => ISA::Hexagon,
             0xB7 => ISA::AArch64,
+            0xBD => ISA::MicroBlaze,
             0xF3 => ISA::RISC_V,
-            _    => return None,
-        };
+            0xF7 => ISA::BPF,
+            0x102 => ISA::LoongArch,
+            _    => ISA::Unknown(value),
+        }
+    }
+
+    pub fn raw(&self) -> u16 {
+        match self {
+            ISA::NONE           => 0x00,
+            ISA::SPARC          => 0x02,
+            ISA::x86            => 0x03,
+            ISA::M68k           => 0x04,
+            ISA::MIPS           => 0x08,
+            ISA::PowerPC        => 0x14,
+            ISA::PowerPC64      => 0x15,
+            ISA::S390           => 0x16,
+            ISA::ARM            => 0x28,
+            ISA::SuperH         => 0x2A,
+            ISA::SPARCV9        => 0x2B,
+            ISA::TriCore        => 0x2C,
+            ISA::IA_64          => 0x32,
+            ISA::x86_64         => 0x3E,
+            ISA::AVR            => 0x53,
+            ISA::Xtensa         => 0x5E,
+            ISA::MSP430         => 0x69,
+            ISA::Hexagon        => 0xA4,
+            ISA::AArch64        => 0xB7,
+            ISA::MicroBlaze     => 0xBD,
+            ISA::RISC_V         => 0xF3,
+            ISA::BPF            => 0xF7,
+            ISA::LoongArch      => 0x102,
+            ISA::Unknown(value) => *value,
+        }
+    }
 
-        Some(isa)
+    fn readelf_name(&self) -> String {
+        match self {
+            ISA::NONE           => "None".to_string(),
+            ISA::SPARC          => "Sparc".to_string(),
+            ISA::x86            => "Intel 80386".to_string(),
+            ISA::M68k           => "MC68000".to_string(),
+            ISA::MIPS           => "MIPS R3000".to_string(),
+            ISA::PowerPC        => "PowerPC".to_string(),
+            ISA::PowerPC64      => "PowerPC64".to_string(),
+            ISA::S390           => "IBM S/390".to_string(),
+            ISA::ARM            => "ARM".to_string(),
+            ISA::SuperH         => "Renesas / SuperH SH".to_string(),
+            ISA::SPARCV9        => "Sparc v9".to_string(),
+            ISA::TriCore        => "Siemens Tricore".to_string(),
+            ISA::IA_64          => "Intel IA-64".to_string(),
+            ISA::x86_64         => "Advanced Micro Devices X86-64".to_string(),
+            ISA::AVR            => "Atmel AVR 8-bit microcontroller".to_string(),
+            ISA::Xtensa         => "Tensilica Xtensa Processor".to_string(),
+            ISA::MSP430         => "Texas Instruments msp430 microcontroller".to_string(),
+            ISA::Hexagon        => "QUALCOMM DSP6 Processor".to_string(),
+            ISA::AArch64        => "AArch64".to_string(),
+            ISA::MicroBlaze     => "Xilinx MicroBlaze".to_string(),
+            ISA::RISC_V         => "RISC-V".to_string(),
+            ISA::BPF            => "Linux BPF".to_string(),
+            ISA::LoongArch      => "LoongArch".to_string(),
+            ISA::Unknown(value) => format!("<unknown>: 0x{:x}", value),
+        }
     }
 }
@@ -0,0 +1,41 @@
+use core::fmt;
+use core::mem;
+
+use num::cast;
+
+use super::{Class, ELF, ISA, ObjectFileType};
+use super::prelude::*;
+
+/// The width-independent parts of an ELF file, so code can handle both
+/// classes through one `dyn ElfInfo` without being generic over `T`.
+pub trait ElfInfo {
+    fn class(&self) -> Class;
+    fn object_type(&self) -> ObjectFileType;
+    fn isa(&self) -> ISA;
+    fn entry_point(&self) -> u64;
+    /// Section names in section header order.
+    fn sections(&self) -> Vec<&str>;
+    /// `.symtab` symbol names in table order.
+    fn symbols(&self) -> Vec<&str>;
+}
+
+impl<T, D> ElfInfo for ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + cast::AsPrimitive<u64>,
+          D: AsRef<[u8]>
+{
+    fn class(&self) -> Class {
+        if mem::size_of::<T>() == 4 { Class::ELF32 } else { Class::ELF64 }
+    }
+
+    fn object_type(&self) -> ObjectFileType { self.header.object_type() }
+    fn isa(&self) -> ISA { self.header.isa() }
+    fn entry_point(&self) -> u64 { self.header.entry_point_u64() }
+
+    fn sections(&self) -> Vec<&str> {
+        self.section_headers.iter().map(|sh| sh.name_str()).collect()
+    }
+
+    fn symbols(&self) -> Vec<&str> {
+        self.symbols.iter().map(|sym| sym.name_str()).collect()
+    }
+}
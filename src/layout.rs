@@ -0,0 +1,84 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, SectionType};
+use super::prelude::*;
+
+/// Where a section sits in the file, relative to the section before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutEntry<T> {
+    name: String,
+    offset: T,
+    size: T,
+    gap_before: T,
+    overlaps: bool,
+}
+
+impl<T> LayoutEntry<T>
+    where T: Copy
+{
+    pub fn name(&self) -> String { self.name.clone() }
+    pub fn offset(&self) -> T { self.offset }
+    pub fn size(&self) -> T { self.size }
+    /// Bytes between the end of the preceding sections and this one's start.
+    pub fn gap_before(&self) -> T { self.gap_before }
+    /// Whether this section starts before the preceding sections end.
+    pub fn overlaps(&self) -> bool { self.overlaps }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// Lists sections in file offset order with the padding before each one
+    /// and whether it overlaps the sections before it.
+    ///
+    /// `NoBits` sections occupy no file space and are left out, as is the null section.
+    pub fn layout_report(&self) -> Vec<LayoutEntry<T>> {
+        let mut sections = self.section_headers.iter()
+            .filter(|sh| sh.section_type() != SectionType::Null && sh.section_type() != SectionType::NoBits)
+            .collect::<Vec<_>>();
+        sections.sort_by_key(|sh| sh.file_offset());
+
+        let mut end = T::zero();
+        sections.into_iter().map(|sh| {
+            let offset = sh.file_offset();
+            let entry = LayoutEntry {
+                name: sh.name(),
+                offset,
+                size: sh.size(),
+                gap_before: offset.saturating_sub(end),
+                overlaps: offset < end,
+            };
+
+            end = end.max(offset.saturating_add(sh.size()));
+            entry
+        }).collect()
+    }
+
+    /// Returns the bytes after everything the headers account for: the ELF
+    /// header, both header tables, and every section's and segment's contents.
+    /// Such an overlay holds appended archives, installers or signatures.
+    ///
+    /// `None` if the file ends with its last structure.
+    pub fn trailing_data(&self) -> Option<&[u8]> {
+        let table_end = |offset: T, count: usize, size: u16| {
+            offset.as_().saturating_add(count.saturating_mul(size as usize))
+        };
+
+        let sections = self.section_headers.iter()
+            .filter(|sh| sh.section_type() != SectionType::NoBits)
+            .map(|sh| sh.file_offset().as_().saturating_add(sh.size().as_()));
+        let segments = self.program_headers.iter()
+            .map(|ph| ph.offset().as_().saturating_add(ph.file_size().as_()));
+
+        let end = [
+            self.header.header_size() as usize,
+            table_end(self.header.ph_offset(), self.program_headers.len(), self.header.ph_size()),
+            table_end(self.header.sh_offset(), self.section_headers.len(), self.header.sh_size()),
+        ].iter().copied().chain(sections).chain(segments).max().unwrap_or(0);
+
+        self.data().get(end..).filter(|rest| !rest.is_empty())
+    }
+}
@@ -1,79 +1,900 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 extern crate num;
 
+mod archive;
+#[cfg(feature = "compression")]
+mod compression;
+mod coredump;
+mod diff;
+#[cfg(feature = "hash")]
+mod digest;
+mod dynamic;
+mod error;
+mod file;
+mod format;
+mod group;
+mod hardening;
+mod hash;
 mod header;
+mod info;
+mod layout;
+mod mode;
+mod note;
+mod profile;
 mod program_header;
+mod reader;
+mod relocation;
 mod section_header;
+pub mod sections;
+mod strtab;
+mod summary;
+mod symbol;
+mod unwind;
+mod version;
+mod warning;
+mod write;
+
+// `alloc` items that `std` would bring in through its prelude
+mod prelude {
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
 
-use std::fmt;
-use std::fs;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use alloc::collections::BTreeSet;
+use core::fmt;
+use prelude::*;
 use num::cast;
 
-pub use header::{Header, Class};
-pub use program_header::ProgramHeader;
-pub use section_header::{SectionHeader, SectionType};
+pub use archive::Archive;
+pub use coredump::CoreInfo;
+pub use diff::ElfDiff;
+pub use dynamic::{DynFlags, DynamicEntry, DynamicTag};
+pub use error::ElfError;
+#[cfg(feature = "std")]
+pub use file::parse_file;
+pub use file::ElfFile;
+pub use format::{FormatOptions, Width};
+pub use group::SectionGroup;
+pub use hardening::{CetInfo, Linkage, PieStatus, Relro};
+pub use header::{Header, Class, Endian, ISA, ObjectFileType, get_elf_class, get_elf_endian};
+pub use info::ElfInfo;
+pub use layout::LayoutEntry;
+pub use mode::ParseMode;
+pub use note::Note;
+pub use profile::SizeProfile;
+pub use program_header::{LoadSegment, ProgramHeader, ProgramType, SegmentFlags, TlsTemplate};
+pub use reader::Reader;
+pub use relocation::{PltEntry, Relocation};
+pub use section_header::{SectionCategory, SectionHeader, SectionType, SpecialIndex, WellKnownSection};
+pub use summary::ElfSummary;
+pub use symbol::{Symbol, SymbolBinding, SymbolStats, SymbolType, Visibility};
+pub use version::VersionedSymbol;
+pub use write::ElfWriter;
 
-pub struct ELF<T>
+pub struct ELF<T, D = Vec<u8>>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
 {
     header: Header<T>,
     program_headers: Vec<ProgramHeader<T>>,
     section_headers: Vec<SectionHeader<T>>,
-    data: Vec<u8>,
+    symbols: Vec<Symbol<T>>,
+    dynamic_symbols: Vec<Symbol<T>>,
+    dynamic: Vec<DynamicEntry<T>>,
+    warnings: Vec<String>,
+    data: D,
 }
 
-impl<T> ELF<T>
-    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+/// An `ELF` borrowing the bytes it was parsed from, as returned by `ELF::parse`.
+pub type ElfRef<'a, T> = ELF<T, &'a [u8]>;
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy,
+          D: AsRef<[u8]>
 {
     pub fn header(&self) -> &Header<T> {
         &self.header
     }
 
-    pub fn program_headers(&self) -> &Vec<ProgramHeader<T>> {
+    pub fn program_headers(&self) -> &[ProgramHeader<T>] {
         &self.program_headers
     }
 
-    pub fn section_headers(&self) -> &Vec<SectionHeader<T>> {
+    pub fn section_headers(&self) -> &[SectionHeader<T>] {
         &self.section_headers
     }
 
-    pub fn data(&self) -> &Vec<u8> {
-        &self.data
+    /// Returns the section at `index`. Reserved indices such as `SHN_ABS`
+    /// should be classified with `SpecialIndex::new` first.
+    pub fn section(&self, index: usize) -> Option<&SectionHeader<T>> {
+        self.section_headers.get(index)
+    }
+
+    /// Pairs each section with its index, as numbered in `readelf -S`.
+    pub fn enumerated_sections(&self) -> impl Iterator<Item = (usize, &SectionHeader<T>)> {
+        self.section_headers.iter().enumerate()
+    }
+
+    /// Pairs each segment with its index, as numbered in `readelf -l`.
+    pub fn enumerated_segments(&self) -> impl Iterator<Item = (usize, &ProgramHeader<T>)> {
+        self.program_headers.iter().enumerate()
+    }
+
+    /// Returns the first segment of type `t`, for types such as `PT_INTERP`
+    /// that appear at most once. `Loos` and `Loproc` match on their raw value.
+    pub fn segment_by_type(&self, t: ProgramType) -> Option<&ProgramHeader<T>> {
+        self.segments_by_type(t).next()
+    }
+
+    /// Returns every segment of type `t` in header order.
+    pub fn segments_by_type(&self, t: ProgramType) -> impl Iterator<Item = &ProgramHeader<T>> {
+        self.program_headers.iter().filter(move |ph| ph.program_type() == t)
+    }
+
+    pub fn symbols(&self) -> &[Symbol<T>] {
+        &self.symbols
+    }
+
+    pub fn dynamic_symbols(&self) -> &[Symbol<T>] {
+        &self.dynamic_symbols
+    }
+
+    pub fn dynamic(&self) -> &[DynamicEntry<T>] {
+        &self.dynamic
+    }
+
+    /// Inconsistencies between the header and the file that parsing tolerated,
+    /// such as an `e_shentsize` that doesn't match the class or sections
+    /// extending past the end of the file.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// Returns the index of the `PT_LOAD` segment that contains `section`.
+    ///
+    /// Allocated sections (including `NoBits` ones such as `.bss`, which have
+    /// no file bytes) are matched by virtual address range, other sections by
+    /// file range.
+    pub fn section_to_segment(&self, section: &SectionHeader<T>) -> Option<usize> {
+        if section.section_type() == SectionType::Null { return None }
+
+        let by_vaddr = section.is_alloc() || section.section_type() == SectionType::NoBits;
+        let (start, size) =
+            if by_vaddr { (section.target_addr().as_(), section.size().as_()) }
+            else { (section.file_offset().as_(), section.size().as_()) };
+        let end = start.checked_add(size)?;
+
+        self.program_headers.iter().position(|ph| {
+            let (seg_start, seg_size) = match ph.program_type() {
+                ProgramType::Load if by_vaddr => (ph.vaddr().as_(), ph.memory_size().as_()),
+                ProgramType::Load => (ph.offset().as_(), ph.file_size().as_()),
+                _ => return false,
+            };
+
+            match seg_start.checked_add(seg_size) {
+                Some(seg_end) => start >= seg_start && end <= seg_end && (size == 0 || start < seg_end),
+                None => false,
+            }
+        })
+    }
+
+    /// Lints the section layout, returning one message per section that is
+    /// allocated but outside every `PT_LOAD` segment (so it never gets mapped),
+    /// or whose contents extend past the end of the file.
+    ///
+    /// Relocatable files have no segments and only get the file range check.
+    /// `.tbss` is exempt from the segment check: it occupies no memory of its
+    /// own outside the TLS blocks.
+    pub fn verify_containment(&self) -> Vec<String> {
+        let is_rel = self.header.object_type() == ObjectFileType::REL;
+        let mut violations = Vec::new();
+
+        for section in self.section_headers.iter().filter(|sh| sh.section_type() != SectionType::Null) {
+            let is_tbss = section.section_type() == SectionType::NoBits && section.flags().as_() & 0x400 != 0;
+            if !is_rel && section.is_alloc() && !is_tbss && self.section_to_segment(section).is_none() {
+                violations.push(format!(
+                    "section {} (0x{:x}..0x{:x}) is allocated but not inside any PT_LOAD segment",
+                    section.name(), section.target_addr(), section.target_addr().saturating_add(section.size()),
+                ));
+            }
+
+            if section.section_type() != SectionType::NoBits && section.file_range(self.data().len()).is_none() {
+                violations.push(format!(
+                    "section {} (offset 0x{:x}, size 0x{:x}) extends past the end of the file",
+                    section.name(), section.file_offset(), section.size(),
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Checks that the header tables, every segment and every section with
+    /// file contents lie within `data`, so no accessor reads past its end.
+    pub fn data_matches(&self) -> bool {
+        let len = self.data().len();
+        let table_fits = |offset: T, count: usize, size: u16| {
+            count.checked_mul(size as usize)
+                .and_then(|bytes| offset.as_().checked_add(bytes))
+                .is_some_and(|end| end <= len)
+        };
+
+        table_fits(self.header.ph_offset(), self.header.ph_num() as usize, self.header.ph_size())
+            && (self.section_headers.is_empty() || table_fits(self.header.sh_offset(), self.section_headers.len(), self.header.sh_size()))
+            && self.program_headers.iter().all(|ph| {
+                ph.offset().as_().checked_add(ph.file_size().as_()).is_some_and(|end| end <= len)
+            })
+            && self.section_headers.iter().all(|sh| {
+                matches!(sh.section_type(), SectionType::Null | SectionType::NoBits) || sh.file_range(len).is_some()
+            })
+    }
+
+    // what `reparse_consistency` compares against a fresh parse of `data`
+    fn same_parse<E: AsRef<[u8]>>(&self, other: &ELF<T, E>) -> bool {
+        self.header == other.header
+            && self.program_headers.len() == other.program_headers.len()
+            && self.symbols.len() == other.symbols.len()
+            && self.dynamic_symbols.len() == other.dynamic_symbols.len()
+            && self.dynamic.len() == other.dynamic.len()
+            && self.section_headers.iter().map(|sh| sh.name_str()).eq(other.section_headers.iter().map(|sh| sh.name_str()))
+    }
+
+    /// Whether there is no `SHT_SYMTAB` section, leaving only `.dynsym`, if any.
+    pub fn is_stripped(&self) -> bool {
+        self.section_headers.iter().all(|sh| sh.section_type() != SectionType::SymTab)
+    }
+
+    /// Iterates over every section paired with its enclosing `PT_LOAD` segment,
+    /// mirroring the "Section to Segment mapping" table of `readelf -l`.
+    pub fn section_segment_mapping(&self) -> impl Iterator<Item = (&SectionHeader<T>, Option<usize>)> {
+        self.section_headers
+            .iter()
+            .map(move |sh| (sh, self.section_to_segment(sh)))
+    }
+
+    /// Counts symbols from `.symtab`, falling back to `.dynsym` for stripped binaries.
+    pub fn symbol_stats(&self) -> SymbolStats {
+        if self.symbols.is_empty() { SymbolStats::new(&self.dynamic_symbols) }
+        else { SymbolStats::new(&self.symbols) }
+    }
+
+    /// Groups `.symtab` (or `.dynsym` for stripped binaries) symbols by the
+    /// name of the section they are defined in.
+    ///
+    /// Undefined, absolute and common symbols go under `*UND*`, `*ABS*` and
+    /// `*COM*` like `objdump -t` prints them.
+    #[cfg(feature = "std")]
+    pub fn symbols_by_section(&self) -> HashMap<String, Vec<&Symbol<T>>> {
+        let symbols = if self.symbols.is_empty() { &self.dynamic_symbols } else { &self.symbols };
+        let mut groups: HashMap<String, Vec<&Symbol<T>>> = HashMap::new();
+
+        // the first entry is the reserved null symbol
+        for sym in symbols.iter().skip(1) {
+            let key = match (sym.section_index(), SpecialIndex::new(sym.shndx())) {
+                (Some(index), _) => match self.section(index) {
+                    Some(section) => section.name(),
+                    None => continue,
+                },
+                (None, Some(SpecialIndex::Undef)) => "*UND*".to_string(),
+                (None, Some(SpecialIndex::Abs)) => "*ABS*".to_string(),
+                (None, Some(SpecialIndex::Common)) => "*COM*".to_string(),
+                (None, Some(special)) => format!("*{:?}*", special),
+                (None, None) => continue,
+            };
+
+            groups.entry(key).or_default().push(sym);
+        }
+
+        groups
+    }
+
+    /// Returns the symbols from `.symtab` and `.dynsym` whose raw name matches
+    /// `pattern`, where `*` matches any run of characters. Symbols present in
+    /// both tables with the same name and value are returned once.
+    pub fn find_symbols(&self, pattern: &str) -> Vec<&Symbol<T>> {
+        let mut seen = BTreeSet::new();
+
+        self.symbols.iter()
+            .chain(self.dynamic_symbols.iter())
+            .filter(|sym| glob_match(pattern, &sym.name()))
+            .filter(|sym| seen.insert((sym.name(), sym.value().as_())))
+            .collect()
+    }
+
+    /// Returns the `.dynsym` entries other objects can bind to: defined,
+    /// `GLOBAL` or `WEAK`, and `DEFAULT` or `PROTECTED` visibility.
+    pub fn exported_symbols(&self) -> Vec<&Symbol<T>> {
+        self.dynamic_symbols.iter()
+            .filter(|sym| sym.shndx() != 0 && matches!(sym.binding(), SymbolBinding::Global | SymbolBinding::Weak))
+            .filter(|sym| matches!(sym.visibility(), Visibility::Default | Visibility::Protected))
+            .collect()
+    }
+
+    /// Returns the undefined `GLOBAL` or `WEAK` `.dynsym` entries, which the
+    /// dynamic linker resolves from other objects.
+    pub fn imported_symbols(&self) -> Vec<&Symbol<T>> {
+        self.dynamic_symbols.iter()
+            .filter(|sym| sym.shndx() == 0 && matches!(sym.binding(), SymbolBinding::Global | SymbolBinding::Weak))
+            .collect()
+    }
+
+    /// Returns the toolchain banners recorded in `.comment`, such as
+    /// `GCC: (GNU) 13.2.0`. Empty when the section is absent.
+    pub fn producers(&self) -> Vec<String> {
+        let comment = match self.section_headers.iter().find(|sh| sh.name() == sections::COMMENT) {
+            Some(section) => section,
+            None => return Vec::new(),
+        };
+        let bytes = match comment.file_range(self.data().len()) {
+            Some(range) => &self.data()[range],
+            None => return Vec::new(),
+        };
+
+        bytes.split(|&byte| byte == 0)
+            .filter(|producer| !producer.is_empty())
+            .map(|producer| String::from_utf8_lossy(producer).into_owned())
+            .collect()
+    }
+
+    /// Lists the name and size of every DWARF section: those named `.debug_*`,
+    /// including split `.dwo` ones, and the legacy compressed `.zdebug_*`.
+    pub fn debug_sections(&self) -> Vec<(&str, T)> {
+        self.section_headers.iter()
+            .filter(|sh| sh.name_str().starts_with(".debug_") || sh.name_str().starts_with(".zdebug_"))
+            .map(|sh| (sh.name_str(), sh.size()))
+            .collect()
+    }
+
+    /// Returns the allocated section whose virtual address range contains `addr`.
+    pub fn section_at_vaddr(&self, addr: T) -> Option<&SectionHeader<T>> {
+        let addr = addr.as_();
+
+        self.section_headers.iter().find(|sh| {
+            if !sh.is_alloc() { return false }
+
+            let start = sh.target_addr().as_();
+            match start.checked_add(sh.size().as_()) {
+                Some(end) => addr >= start && addr < end,
+                None => false,
+            }
+        })
+    }
+
+    /// Resolves `addr` to the function symbol containing it, returning the
+    /// symbol name and the offset of `addr` into that symbol.
+    ///
+    /// Symbols with a zero size can't contain anything, so the nearest
+    /// preceding one of them is used when no sized symbol matches.
+    pub fn symbolize(&self, addr: T) -> Option<(String, T)> {
+        let symbols = if self.symbols.is_empty() { &self.dynamic_symbols } else { &self.symbols };
+        let functions = || {
+            symbols.iter().filter(|sym| {
+                sym.sym_type() == SymbolType::Func && sym.shndx() != 0 && sym.value() <= addr
+            })
+        };
+
+        let containing = functions().find(|sym| {
+            match sym.value().checked_add(&sym.size()) {
+                Some(end) => sym.size() != T::zero() && addr < end,
+                None => false,
+            }
+        });
+        let symbol = containing.or_else(|| {
+            functions()
+                .filter(|sym| sym.size() == T::zero())
+                .max_by_key(|sym| sym.value())
+        })?;
+
+        Some((symbol.name(), addr - symbol.value()))
+    }
+
+    /// Returns the name of the `.symtab` symbol at the entry point, usually
+    /// `_start`, preferring function symbols. `None` for stripped binaries
+    /// and files without an entry point.
+    ///
+    /// On ARM the Thumb bit (bit 0) is ignored on both sides of the comparison.
+    pub fn entry_symbol(&self) -> Option<String> {
+        let mask = if self.header.isa() == ISA::ARM { !T::one() } else { !T::zero() };
+        let entry = self.header.entry_point() & mask;
+        if entry.is_zero() { return None }
+
+        let candidates = || {
+            self.symbols.iter().filter(move |sym| {
+                let named = sym.sym_type() != SymbolType::Section && sym.sym_type() != SymbolType::File;
+                named && sym.shndx() != 0 && sym.value() & mask == entry
+            })
+        };
+
+        candidates()
+            .find(|sym| sym.sym_type() == SymbolType::Func)
+            .or_else(|| candidates().next())
+            .map(|sym| sym.name())
+    }
+
+    /// Iterates over `PT_LOAD` segments with their file and memory ranges.
+    /// Segments whose ranges overflow `T` are skipped.
+    pub fn loadable_segments(&self) -> impl Iterator<Item = LoadSegment<T>> + '_ {
+        self.program_headers.iter().filter_map(|ph| {
+            if ph.program_type() != ProgramType::Load { return None }
+
+            let file_end = ph.offset().checked_add(&ph.file_size())?;
+            let mem_end = ph.vaddr().checked_add(&ph.memory_size())?;
+
+            Some(LoadSegment {
+                file_range: ph.offset()..file_end,
+                mem_range: ph.vaddr()..mem_end,
+                flags: SegmentFlags::new(ph.flags()),
+                align: ph.align(),
+            })
+        })
+    }
+
+    /// Returns the thread-local storage template from the `PT_TLS` segment,
+    /// or `None` if the binary has no thread-local variables.
+    pub fn tls_template(&self) -> Option<TlsTemplate<T>> {
+        let ph = self.program_headers.iter().find(|ph| ph.program_type() == ProgramType::Tls)?;
+
+        Some(TlsTemplate {
+            offset: ph.offset(),
+            vaddr: ph.vaddr(),
+            file_size: ph.file_size(),
+            memory_size: ph.memory_size(),
+            align: ph.align(),
+        })
+    }
+
+    /// Returns the `len` file-backed bytes mapped at virtual address `addr`.
+    ///
+    /// `None` if the range isn't inside a single `PT_LOAD` segment or reaches
+    /// into its zero-filled tail; see `read_at_vaddr_zeroed` for those reads.
+    pub fn read_at_vaddr(&self, addr: T, len: usize) -> Option<&[u8]> {
+        let (ph, delta) = self.load_segment_at(addr.as_(), len)?;
+        if delta.checked_add(len)? > ph.file_size().as_() { return None }
+
+        let start = ph.offset().as_().checked_add(delta)?;
+        self.data().get(start..start.checked_add(len)?)
+    }
+
+    /// Returns `len` bytes starting at the entry point, for feeding a disassembler.
+    /// `None` if the entry point isn't backed by a `PT_LOAD` segment.
+    pub fn entry_bytes(&self, len: usize) -> Option<&[u8]> {
+        self.read_at_vaddr(self.header.entry_point(), len)
+    }
+
+    /// Like `read_at_vaddr`, but copies the bytes out so that reads reaching
+    /// into the zero-filled (BSS) part of a segment succeed.
+    pub fn read_at_vaddr_zeroed(&self, addr: T, len: usize) -> Option<Vec<u8>> {
+        let (ph, delta) = self.load_segment_at(addr.as_(), len)?;
+        let file_size = ph.file_size().as_();
+        let backed = if delta < file_size { (file_size - delta).min(len) } else { 0 };
+
+        let start = ph.offset().as_().checked_add(delta)?;
+        let mut bytes = vec![0; len];
+        if backed > 0 {
+            bytes[..backed].copy_from_slice(self.data().get(start..start.checked_add(backed)?)?);
+        }
+
+        Some(bytes)
+    }
+
+    /// Returns the lowest `vaddr` and the highest `vaddr + memory_size` over all
+    /// `PT_LOAD` segments, i.e. the address range a loader has to reserve.
+    ///
+    /// `None` if there are no loadable segments or a segment end overflows `T`.
+    pub fn image_span(&self) -> Option<(T, T)> {
+        let mut span = None;
+
+        for ph in self.program_headers.iter().filter(|ph| ph.program_type() == ProgramType::Load) {
+            let end = ph.vaddr().checked_add(&ph.memory_size())?;
+            span = match span {
+                Some((low, high)) => Some((ph.vaddr().min(low), end.max(high))),
+                None => Some((ph.vaddr(), end)),
+            };
+        }
+
+        span
+    }
+
+    /// Lays out the `PT_LOAD` segments in a zeroed memory image whose first
+    /// byte is address `base`, the way a loader maps them: `file_size` bytes
+    /// are copied to `vaddr - base` and the rest up to `memory_size` stays zero.
+    ///
+    /// Pass `image_span().0` for the smallest image, or that address aligned
+    /// down to the segment alignment to keep offsets page aligned.
+    /// No relocations are applied.
+    pub fn flatten(&self, base: T) -> Result<Vec<u8>, ElfError> {
+        let segments = self.segments_by_vaddr();
+        let (low, high) = match self.image_span() {
+            Some(span) => span,
+            None if segments.is_empty() => return Ok(Vec::new()),
+            None => return Err(ElfError::InvalidProgramHeaders),
+        };
+        if base > low { return Err(ElfError::InvalidLoadBase) }
+
+        if !self.segment_overlaps().is_empty() { return Err(ElfError::OverlappingSegments) }
+
+        let size = (high - base).as_();
+        let mut image = Vec::new();
+        image.try_reserve_exact(size).map_err(|_| ElfError::ImageTooLarge)?;
+        image.resize(size, 0);
+
+        for ph in segments {
+            let size = ph.file_size().min(ph.memory_size()).as_();
+            let offset = ph.offset().as_();
+            let bytes = offset.checked_add(size)
+                .and_then(|end| self.data().get(offset..end))
+                .ok_or(ElfError::InvalidProgramHeaders)?;
+
+            let start = (ph.vaddr() - base).as_();
+            image[start..start + size].copy_from_slice(bytes);
+        }
+
+        Ok(image)
+    }
+
+    /// Returns the index pairs, lower index first, of `PT_LOAD` segments whose
+    /// `[vaddr, vaddr + memory_size)` ranges overlap. Other segment types
+    /// normally lie inside a `PT_LOAD` one and are ignored.
+    pub fn segment_overlaps(&self) -> Vec<(usize, usize)> {
+        // a range running past the top of the address space ends there
+        let mut ranges = self.program_headers.iter()
+            .enumerate()
+            .filter(|(_, ph)| ph.program_type() == ProgramType::Load && !ph.memory_size().is_zero())
+            .map(|(index, ph)| (ph.vaddr(), ph.vaddr().checked_add(&ph.memory_size()).unwrap_or_else(T::max_value), index))
+            .collect::<Vec<_>>();
+        ranges.sort();
+
+        let mut overlaps = Vec::new();
+        for (position, &(_, end, index)) in ranges.iter().enumerate() {
+            // sorted by start, so only the ranges starting before this one ends can overlap it
+            for &(_, _, other) in ranges[position + 1..].iter().take_while(|(start, _, _)| *start < end) {
+                overlaps.push((index.min(other), index.max(other)));
+            }
+        }
+
+        overlaps.sort();
+        overlaps
+    }
+
+    /// Returns the `PT_LOAD` segments sorted by virtual address. The stored
+    /// program headers keep their file order.
+    pub fn segments_by_vaddr(&self) -> Vec<&ProgramHeader<T>> {
+        let mut segments = self.all_segments_by_vaddr();
+        segments.retain(|ph| ph.program_type() == ProgramType::Load);
+        segments
+    }
+
+    /// Returns every segment, of any type, sorted by virtual address.
+    pub fn all_segments_by_vaddr(&self) -> Vec<&ProgramHeader<T>> {
+        let mut segments = self.program_headers.iter().collect::<Vec<_>>();
+        segments.sort_by_key(|ph| ph.vaddr());
+        segments
+    }
+
+    /// Returns the indices of segments with more file bytes than memory,
+    /// which no loader can map as described.
+    pub fn malformed_segments(&self) -> Vec<usize> {
+        self.program_headers.iter()
+            .enumerate()
+            .filter(|(_, ph)| ph.file_size() > ph.memory_size())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns the indices of segments failing `ProgramHeader::is_properly_aligned`.
+    pub fn alignment_violations(&self) -> Vec<usize> {
+        self.program_headers.iter()
+            .enumerate()
+            .filter(|(_, ph)| !ph.is_properly_aligned())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn load_segment_at(&self, addr: usize, len: usize) -> Option<(&ProgramHeader<T>, usize)> {
+        let end = addr.checked_add(len)?;
+
+        self.program_headers.iter().find(|ph| {
+            let vaddr = ph.vaddr().as_();
+            let is_load = ph.program_type() == ProgramType::Load;
+
+            match vaddr.checked_add(ph.memory_size().as_()) {
+                Some(mem_end) => is_load && addr >= vaddr && end <= mem_end,
+                None => false,
+            }
+        }).map(|ph| (ph, addr - ph.vaddr().as_()))
     }
 }
 
 impl ELF<u32>
 {
     pub fn new(binary: Vec<u8>) -> Option<ELF<u32>> {
-        let header = Header::<u32>::new(&binary)?;
-        let program_headers = ProgramHeader::<u32>::new(&binary, &header)?;
-        let section_headers = SectionHeader::<u32>::new(&binary, &header)?;
+        Self::from_vec(binary, ParseMode::Strict).ok()
+    }
 
-        Some(ELF {
+    /// Like `new`, but with `ParseMode::Lenient` keeps what can be decoded
+    /// of a damaged file.
+    pub fn new_with_mode(binary: Vec<u8>, mode: ParseMode) -> Option<ELF<u32>> {
+        Self::from_vec(binary, mode).ok()
+    }
+
+    // `new_with_mode` with the reason for rejecting `binary`
+    pub(crate) fn from_vec(binary: Vec<u8>, mode: ParseMode) -> Result<ELF<u32>, ElfError> {
+        let ELF {
             header,
             program_headers,
             section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            ..
+        } = ELF::<u32>::parse_with_mode(&binary, mode)?;
+
+        Ok(ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
             data: binary,
         })
     }
+
+    /// Parses an ELF embedded at `base` in a larger buffer, such as a firmware
+    /// image. File offsets in the result are relative to `base`.
+    pub fn parse_at(data: &[u8], base: usize) -> Result<ElfRef<'_, u32>, ElfError> {
+        match data.get(base..) {
+            Some(rest) => Self::parse(rest),
+            None => Err(ElfError::Truncated { needed: 0x34, offset: base, len: data.len() }),
+        }
+    }
+
+    /// Parses `data` without copying it; the result borrows `data`.
+    pub fn parse(data: &[u8]) -> Result<ElfRef<'_, u32>, ElfError> {
+        Self::parse_with_mode(data, ParseMode::Strict)
+    }
+
+    /// Like `parse`, but with `ParseMode::Lenient` replaces undecodable entries
+    /// and tables with null or empty ones instead of failing, listing them in `warnings`.
+    pub fn parse_with_mode(data: &[u8], mode: ParseMode) -> Result<ElfRef<'_, u32>, ElfError> {
+        check_ident(data, Class::ELF32, 0x34)?;
+
+        let mut skipped = Vec::new();
+        let header = Header::<u32>::new(data).ok_or(ElfError::InvalidHeader)?;
+        let program_headers = mode.collect(Some(ProgramHeader::<u32>::each(data, &header)), "program headers", &mut skipped)
+            .ok_or_else(|| {
+                let (offset, count, stride) = (header.ph_offset() as usize, header.ph_num() as usize, header.ph_size() as usize);
+                truncated_table(data, offset, count, stride, 0x20).unwrap_or(ElfError::InvalidProgramHeaders)
+            })?;
+        let section_headers = mode.collect(SectionHeader::<u32>::each(data, &header), "section headers", &mut skipped)
+            .ok_or_else(|| SectionHeader::<u32>::truncation(data, &header).unwrap_or(ElfError::InvalidSectionHeaders))?;
+        let symbols = match find_section(&section_headers, SectionType::SymTab) {
+            Some(symtab) => mode.collect(Symbol::<u32>::each(data, &header, &section_headers, symtab), ".symtab", &mut skipped)
+                .ok_or_else(|| symbols_error(data, symtab))?,
+            None => Vec::new(),
+        };
+        let dynamic_symbols = match find_section(&section_headers, SectionType::DynSym) {
+            Some(dynsym) => mode.collect(Symbol::<u32>::each(data, &header, &section_headers, dynsym), ".dynsym", &mut skipped)
+                .ok_or_else(|| symbols_error(data, dynsym))?,
+            None => Vec::new(),
+        };
+        let dynamic = DynamicEntry::<u32>::new(data, &header, &program_headers, &section_headers);
+        // only the `.dynamic` queries need it, so even strict parsing just warns
+        let dynamic = ParseMode::Lenient.recover(dynamic, ".dynamic", &mut skipped).unwrap_or_default();
+        let mut warnings = warning::collect(data, &header, &program_headers, &section_headers, 0x20, 0x28);
+        warnings.extend(skipped);
+
+        Ok(ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            data,
+        })
+    }
 }
 
 impl ELF<u64>
 {
     pub fn new(binary: Vec<u8>) -> Option<ELF<u64>> {
-        let header = Header::<u64>::new(&binary)?;
-        let program_headers = ProgramHeader::<u64>::new(&binary, &header)?;
-        let section_headers = SectionHeader::<u64>::new(&binary, &header)?;
+        Self::from_vec(binary, ParseMode::Strict).ok()
+    }
 
-        Some(ELF {
+    /// Like `new`, but with `ParseMode::Lenient` keeps what can be decoded
+    /// of a damaged file.
+    pub fn new_with_mode(binary: Vec<u8>, mode: ParseMode) -> Option<ELF<u64>> {
+        Self::from_vec(binary, mode).ok()
+    }
+
+    // `new_with_mode` with the reason for rejecting `binary`
+    pub(crate) fn from_vec(binary: Vec<u8>, mode: ParseMode) -> Result<ELF<u64>, ElfError> {
+        let ELF {
             header,
             program_headers,
             section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            ..
+        } = ELF::<u64>::parse_with_mode(&binary, mode)?;
+
+        Ok(ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
             data: binary,
         })
     }
+
+    /// Parses an ELF embedded at `base` in a larger buffer, such as a firmware
+    /// image. File offsets in the result are relative to `base`.
+    pub fn parse_at(data: &[u8], base: usize) -> Result<ElfRef<'_, u64>, ElfError> {
+        match data.get(base..) {
+            Some(rest) => Self::parse(rest),
+            None => Err(ElfError::Truncated { needed: 0x40, offset: base, len: data.len() }),
+        }
+    }
+
+    /// Parses `data` without copying it; the result borrows `data`.
+    pub fn parse(data: &[u8]) -> Result<ElfRef<'_, u64>, ElfError> {
+        Self::parse_with_mode(data, ParseMode::Strict)
+    }
+
+    /// Like `parse`, but with `ParseMode::Lenient` replaces undecodable entries
+    /// and tables with null or empty ones instead of failing, listing them in `warnings`.
+    pub fn parse_with_mode(data: &[u8], mode: ParseMode) -> Result<ElfRef<'_, u64>, ElfError> {
+        check_ident(data, Class::ELF64, 0x40)?;
+
+        let mut skipped = Vec::new();
+        let header = Header::<u64>::new(data).ok_or(ElfError::InvalidHeader)?;
+        let program_headers = mode.collect(Some(ProgramHeader::<u64>::each(data, &header)), "program headers", &mut skipped)
+            .ok_or_else(|| {
+                let (offset, count, stride) = (header.ph_offset() as usize, header.ph_num() as usize, header.ph_size() as usize);
+                truncated_table(data, offset, count, stride, 0x38).unwrap_or(ElfError::InvalidProgramHeaders)
+            })?;
+        let section_headers = mode.collect(SectionHeader::<u64>::each(data, &header), "section headers", &mut skipped)
+            .ok_or_else(|| SectionHeader::<u64>::truncation(data, &header).unwrap_or(ElfError::InvalidSectionHeaders))?;
+        let symbols = match find_section(&section_headers, SectionType::SymTab) {
+            Some(symtab) => mode.collect(Symbol::<u64>::each(data, &header, &section_headers, symtab), ".symtab", &mut skipped)
+                .ok_or_else(|| symbols_error(data, symtab))?,
+            None => Vec::new(),
+        };
+        let dynamic_symbols = match find_section(&section_headers, SectionType::DynSym) {
+            Some(dynsym) => mode.collect(Symbol::<u64>::each(data, &header, &section_headers, dynsym), ".dynsym", &mut skipped)
+                .ok_or_else(|| symbols_error(data, dynsym))?,
+            None => Vec::new(),
+        };
+        let dynamic = DynamicEntry::<u64>::new(data, &header, &program_headers, &section_headers);
+        // only the `.dynamic` queries need it, so even strict parsing just warns
+        let dynamic = ParseMode::Lenient.recover(dynamic, ".dynamic", &mut skipped).unwrap_or_default();
+        let mut warnings = warning::collect(data, &header, &program_headers, &section_headers, 0x38, 0x40);
+        warnings.extend(skipped);
+
+        Ok(ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            data,
+        })
+    }
+}
+
+impl<'a, T> ElfRef<'a, T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    /// Copies the borrowed bytes so the result outlives the buffer passed
+    /// to `parse`. The parsed tables are moved over as they are.
+    pub fn into_owned(self) -> ELF<T> {
+        let ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            data,
+        } = self;
+
+        ELF {
+            header,
+            program_headers,
+            section_headers,
+            symbols,
+            dynamic_symbols,
+            dynamic,
+            warnings,
+            data: data.to_vec(),
+        }
+    }
+}
+
+impl<D> ELF<u32, D>
+    where D: AsRef<[u8]>
+{
+    /// Decodes section headers from the file one at a time, for callers that
+    /// only need a few of them. `section_headers` has all of them pre-parsed.
+    pub fn section_iter(&self) -> impl Iterator<Item = SectionHeader<u32>> + '_ {
+        SectionHeader::<u32>::lazy(self.data(), &self.header)
+    }
+
+    /// Decodes `.symtab` entries one at a time, resolving each name only when
+    /// the iterator reaches it. `symbols` has all of them pre-parsed.
+    pub fn symbol_iter(&self) -> impl Iterator<Item = Symbol<u32>> + '_ {
+        find_section(&self.section_headers, SectionType::SymTab)
+            .into_iter()
+            .flat_map(move |symtab| Symbol::<u32>::lazy(self.data(), &self.header, &self.section_headers, symtab))
+    }
+
+    /// Parses `data` again and checks the header and the table sizes match
+    /// the stored ones; a cheap regression gate to run over a corpus.
+    pub fn reparse_consistency(&self) -> bool {
+        ELF::<u32>::parse(self.data()).is_ok_and(|reparsed| self.same_parse(&reparsed))
+    }
+}
+
+impl<D> ELF<u64, D>
+    where D: AsRef<[u8]>
+{
+    /// Decodes section headers from the file one at a time, for callers that
+    /// only need a few of them. `section_headers` has all of them pre-parsed.
+    pub fn section_iter(&self) -> impl Iterator<Item = SectionHeader<u64>> + '_ {
+        SectionHeader::<u64>::lazy(self.data(), &self.header)
+    }
+
+    /// Decodes `.symtab` entries one at a time, resolving each name only when
+    /// the iterator reaches it. `symbols` has all of them pre-parsed.
+    pub fn symbol_iter(&self) -> impl Iterator<Item = Symbol<u64>> + '_ {
+        find_section(&self.section_headers, SectionType::SymTab)
+            .into_iter()
+            .flat_map(move |symtab| Symbol::<u64>::lazy(self.data(), &self.header, &self.section_headers, symtab))
+    }
+
+    /// Parses `data` again and checks the header and the table sizes match
+    /// the stored ones; a cheap regression gate to run over a corpus.
+    pub fn reparse_consistency(&self) -> bool {
+        ELF::<u64>::parse(self.data()).is_ok_and(|reparsed| self.same_parse(&reparsed))
+    }
+}
+
+impl<T, D> fmt::Debug for ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    /// Summarizes the file on one line so `dbg!` stays readable; `Display` has the full dump.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} segments={} sections={}", self.header, self.program_headers.len(), self.section_headers.len())
+    }
 }
 
-impl<T> fmt::Display for ELF<T>
+impl<T, D> fmt::Display for ELF<T, D>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -104,13 +925,77 @@ impl<T> fmt::Display for ELF<T>
     }
 }
 
-fn read_binary(filename: &str) -> Vec<u8> {
-    match fs::read(filename) {
-        Ok(bin) => bin,
-        Err(e) => panic!(e),
+fn check_ident(data: &[u8], class: Class, header_size: usize) -> Result<(), ElfError> {
+    if data.len() < header_size { return Err(ElfError::Truncated { needed: header_size, offset: 0, len: data.len() }) }
+    if data[0x0..0x4] != [0x7F, b'E', b'L', b'F'] { return Err(ElfError::InvalidMagic) }
+
+    let expected = match class { Class::ELF32 => 1, Class::ELF64 => 2 };
+    if data[0x4] != expected { return Err(ElfError::ClassMismatch { expected: class, found: data[0x4] }) }
+
+    Ok(())
+}
+
+// `Truncated` when `count` entries of `entry_len` bytes, `stride` apart from
+// `offset`, run past the end of `data` or overflow on the way there
+fn truncated_table(data: &[u8], offset: usize, count: usize, stride: usize, entry_len: usize) -> Option<ElfError> {
+    if count == 0 { return None }
+
+    let needed = (count - 1).checked_mul(stride).and_then(|span| span.checked_add(entry_len));
+    match needed.and_then(|needed| offset.checked_add(needed)) {
+        Some(end) if end <= data.len() => None,
+        _ => Some(ElfError::Truncated { needed: needed.unwrap_or(usize::MAX), offset, len: data.len() }),
     }
 }
 
+// why the symbol table `symtab` couldn't be decoded
+fn symbols_error<T>(data: &[u8], symtab: &SectionHeader<T>) -> ElfError
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    match symtab.file_range(data.len()) {
+        Some(_) => ElfError::InvalidSymbols,
+        None => ElfError::Truncated { needed: symtab.size().as_(), offset: symtab.file_offset().as_(), len: data.len() },
+    }
+}
+
+// two pointers with backtracking to the last `*` only, so matching stays
+// O(pattern * name) however many stars there are; comparing bytes is fine
+// for UTF-8 since `*` is ASCII and literals must match whole
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let (pattern, name) = (pattern.as_bytes(), name.as_bytes());
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            // let the last star swallow one more byte and retry from there
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+fn find_section<T>(sections: &[SectionHeader<T>], section_type: SectionType) -> Option<&SectionHeader<T>>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    sections.iter().find(|sh| sh.section_type() == section_type)
+}
+
 fn make_u16(values: &[u8], is_little_endian: bool) -> u16 {
     if is_little_endian {
         (values[0] as u16) | ((values[1] as u16) << 8)
@@ -139,3 +1024,28 @@ fn make_u64(values: &[u8], is_little_endian: bool) -> u64 {
         acc | ((v as u64) << (index * 8))
     })
 }
+
+fn make_string(binary: &[u8], offset: usize) -> Option<String> {
+    let bytes = binary.get(offset..)?;
+    let end_of_string = bytes.iter().position(|&bin| { bin == 0 })?;
+
+    match core::str::from_utf8(&bytes[..end_of_string]) {
+        Ok(s) => Some(String::from(s)),
+        Err(_) => None,
+    }
+}
+
+fn split_u16(value: u16, is_little_endian: bool) -> Vec<u8> {
+    if is_little_endian { value.to_le_bytes().to_vec() }
+    else { value.to_be_bytes().to_vec() }
+}
+
+fn split_u32(value: u32, is_little_endian: bool) -> Vec<u8> {
+    if is_little_endian { value.to_le_bytes().to_vec() }
+    else { value.to_be_bytes().to_vec() }
+}
+
+fn split_u64(value: u64, is_little_endian: bool) -> Vec<u8> {
+    if is_little_endian { value.to_le_bytes().to_vec() }
+    else { value.to_be_bytes().to_vec() }
+}
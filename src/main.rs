@@ -0,0 +1,90 @@
+extern crate elf_reader;
+extern crate num;
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process;
+
+use num::cast;
+
+use elf_reader::{ELF, ElfFile, FormatOptions};
+
+const USAGE: &str = "usage: elf-reader [--headers] [--sections] [--segments] [--symbols] <file>";
+
+struct Options {
+    headers: bool,
+    sections: bool,
+    segments: bool,
+    symbols: bool,
+}
+
+fn main() {
+    let mut options = Options { headers: false, sections: false, segments: false, symbols: false };
+    let mut path = None;
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "-h" | "--headers" => options.headers = true,
+            "-S" | "--sections" => options.sections = true,
+            "-l" | "--segments" => options.segments = true,
+            "-s" | "--symbols" => options.symbols = true,
+            "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            flag if flag.starts_with('-') => fail(&format!("unknown option {}\n{}", flag, USAGE)),
+            _ if path.is_some() => fail(USAGE),
+            _ => path = Some(arg),
+        }
+    }
+
+    // without any flag, print everything like the original dump did
+    if !(options.headers || options.sections || options.segments || options.symbols) {
+        options = Options { headers: true, sections: true, segments: true, symbols: true };
+    }
+
+    let path = path.unwrap_or_else(|| fail(USAGE));
+    let binary = fs::read(&path).unwrap_or_else(|e| fail(&format!("failed to read {}: {}", path, e)));
+
+    match ElfFile::new(binary) {
+        Some(ElfFile::Elf32(elf)) => dump(&elf, &options),
+        Some(ElfFile::Elf64(elf)) => dump(&elf, &options),
+        None => fail(&format!("{} is not a valid ELF file", path)),
+    }
+}
+
+fn dump<T>(elf: &ELF<T>, options: &Options)
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    let format = FormatOptions::default();
+
+    if options.headers {
+        println!("{}", elf.header().format_readelf());
+    }
+
+    if options.sections {
+        println!("Section Headers:");
+        for (index, sh) in elf.enumerated_sections() {
+            println!("[{}] {}", index, sh.fmt_with(&format));
+        }
+        println!();
+    }
+
+    if options.segments {
+        println!("Program Headers:");
+        for (index, ph) in elf.enumerated_segments() {
+            println!("[{}] {}", index, ph.fmt_with(&format));
+        }
+        println!();
+    }
+
+    if options.symbols {
+        print!("{}", elf.format_symbols());
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
@@ -0,0 +1,56 @@
+use super::prelude::*;
+
+/// What `ELF::parse_with_mode` does with entries it can't decode.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ParseMode {
+    /// Reject the whole file, as `ELF::parse` does.
+    #[default]
+    Strict,
+    /// Replace the entry with a null one (`PT_NULL`, `SHT_NULL` or an
+    /// undefined symbol), or the whole table with an empty one when it can't
+    /// be located, and record it in `ELF::warnings`. The entries after a
+    /// replaced one keep their indices.
+    Lenient,
+}
+
+impl ParseMode {
+    /// Gathers the decoded entries of `table`, where `None` stands for an
+    /// entry, or the whole table, that couldn't be decoded.
+    pub(crate) fn collect<E: Placeholder>(
+        self,
+        entries: Option<impl Iterator<Item = Option<E>>>,
+        table: &str,
+        skipped: &mut Vec<String>,
+    ) -> Option<Vec<E>> {
+        match self {
+            ParseMode::Strict => entries?.collect(),
+            ParseMode::Lenient => {
+                let entries = entries.map(|entries| entries.enumerate().map(|(index, entry)| {
+                    entry.unwrap_or_else(|| {
+                        skipped.push(format!("{}: entry {} could not be parsed", table, index));
+                        E::placeholder()
+                    })
+                }).collect());
+
+                self.recover(entries, table, skipped)
+            }
+        }
+    }
+
+    /// Replaces an unreadable `table` with an empty one in lenient mode.
+    pub(crate) fn recover<E>(self, entries: Option<Vec<E>>, table: &str, skipped: &mut Vec<String>) -> Option<Vec<E>> {
+        match (self, entries) {
+            (ParseMode::Lenient, None) => {
+                skipped.push(format!("{}: could not be parsed", table));
+                Some(Vec::new())
+            }
+            (_, entries) => entries,
+        }
+    }
+}
+
+// `st_shndx`, `sh_link` and `r_sym` refer to entries by position, so lenient
+// parsing fills the gap of an undecodable entry instead of closing it
+pub(crate) trait Placeholder {
+    fn placeholder() -> Self;
+}
@@ -0,0 +1,104 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, ProgramType, SectionType};
+use super::make_u32;
+use super::prelude::*;
+
+/// One entry of a `PT_NOTE` segment or `SHT_NOTE` section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    name: String,
+    note_type: u32,
+    desc: Vec<u8>,
+}
+
+impl Note {
+    /// The owner, e.g. `GNU` or `CORE`.
+    pub fn name(&self) -> String { self.name.clone() }
+    pub fn note_type(&self) -> u32 { self.note_type }
+    pub fn desc(&self) -> &[u8] { &self.desc }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Decodes every note in the `PT_NOTE` segments, or in the `SHT_NOTE`
+    /// sections for files without program headers. A malformed entry ends
+    /// the walk of the segment or section it's in.
+    pub fn notes(&self) -> Vec<Note> {
+        let segments = self.program_headers.iter()
+            .filter(|ph| ph.program_type() == ProgramType::Note)
+            .map(|ph| (ph.offset().as_(), ph.file_size().as_(), ph.align().as_()))
+            .collect::<Vec<_>>();
+        let regions =
+            if !segments.is_empty() { segments }
+            else {
+                self.section_headers.iter()
+                    .filter(|sh| sh.section_type() == SectionType::Note)
+                    .map(|sh| (sh.file_offset().as_(), sh.size().as_(), sh.addr_align().as_()))
+                    .collect()
+            };
+
+        regions.into_iter().flat_map(|(offset, size, align)| {
+            let bytes = offset.checked_add(size).and_then(|end| self.data().get(offset..end)).unwrap_or(&[]);
+            // entries are padded to 4 bytes, or 8 in GNU property notes
+            let align = if align == 8 { 8 } else { 4 };
+            parse_notes(bytes, align, self.header.is_little())
+        }).collect()
+    }
+
+    /// The `NT_GNU_BUILD_ID` note's descriptor, the ID `readelf -n` prints in hex.
+    pub fn build_id(&self) -> Option<Vec<u8>> {
+        self.notes().into_iter()
+            .find(|note| note.name == "GNU" && note.note_type == 3)
+            .map(|note| note.desc)
+    }
+
+    /// Groups `notes` by owner, e.g. every `GNU` note under `"GNU"`, keeping
+    /// the file order within each group.
+    #[cfg(feature = "std")]
+    pub fn notes_by_name(&self) -> HashMap<String, Vec<Note>> {
+        let mut groups: HashMap<String, Vec<Note>> = HashMap::new();
+        for note in self.notes() {
+            groups.entry(note.name.clone()).or_default().push(note);
+        }
+
+        groups
+    }
+}
+
+fn parse_notes(bytes: &[u8], align: usize, is_little: bool) -> Vec<Note> {
+    let padded = |len: usize| len.next_multiple_of(align);
+    let mut notes = Vec::new();
+    let mut offset = 0;
+
+    while let Some(fields) = bytes.get(offset..offset + 0x0C) {
+        let name_size = make_u32(&fields[0x00..0x04], is_little) as usize;
+        let desc_size = make_u32(&fields[0x04..0x08], is_little) as usize;
+        let note_type = make_u32(&fields[0x08..0x0C], is_little);
+
+        // padding aligns positions, so an 8-byte aligned name field ends
+        // at 0x10 and not 0x14
+        let name_start = offset + 0x0C;
+        let desc_start = padded(name_start + name_size);
+        let name = match bytes.get(name_start..name_start + name_size) {
+            Some(name) => name,
+            None => break,
+        };
+        let desc = match bytes.get(desc_start..desc_start + desc_size) {
+            Some(desc) => desc,
+            None => break,
+        };
+
+        let name = String::from_utf8_lossy(name).trim_end_matches('\0').to_string();
+        notes.push(Note { name, note_type, desc: desc.to_vec() });
+        offset = padded(desc_start + desc_size);
+    }
+
+    notes
+}
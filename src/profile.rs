@@ -0,0 +1,64 @@
+use core::cmp;
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, ProgramType, SectionType, SymbolType};
+use super::prelude::*;
+
+/// Where the bytes of a binary go, in the spirit of `bloaty`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizeProfile<T> {
+    file_size: usize,
+    memory_size: T,
+    sections: Vec<(String, T)>,
+    largest_functions: Vec<(String, T)>,
+}
+
+impl<T> SizeProfile<T>
+    where T: Copy
+{
+    pub fn file_size(&self) -> usize { self.file_size }
+    /// Sum of the memory sizes of every `PT_LOAD` segment.
+    pub fn memory_size(&self) -> T { self.memory_size }
+    /// Section names with their size on disk, largest first.
+    pub fn sections(&self) -> &[(String, T)] { &self.sections }
+    /// Function symbol names with their sizes, largest first.
+    pub fn largest_functions(&self) -> &[(String, T)] { &self.largest_functions }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// Summarizes file and memory usage per section, plus the `top_n` largest
+    /// defined functions from `.symtab` (or `.dynsym` for stripped binaries).
+    ///
+    /// `NoBits` sections take no file space and are left out of the section list.
+    pub fn size_profile(&self, top_n: usize) -> SizeProfile<T> {
+        let memory_size = self.program_headers.iter()
+            .filter(|ph| ph.program_type() == ProgramType::Load)
+            .fold(T::zero(), |total, ph| total.saturating_add(ph.memory_size()));
+
+        let mut sections = self.section_headers.iter()
+            .filter(|sh| sh.section_type() != SectionType::Null && sh.section_type() != SectionType::NoBits)
+            .map(|sh| (sh.name(), sh.size()))
+            .collect::<Vec<_>>();
+        sections.sort_by_key(|&(_, size)| cmp::Reverse(size));
+
+        let symbols = if self.symbols.is_empty() { &self.dynamic_symbols } else { &self.symbols };
+        let mut largest_functions = symbols.iter()
+            .filter(|sym| sym.sym_type() == SymbolType::Func && sym.shndx() != 0)
+            .map(|sym| (sym.name(), sym.size()))
+            .collect::<Vec<_>>();
+        largest_functions.sort_by_key(|&(_, size)| cmp::Reverse(size));
+        largest_functions.truncate(top_n);
+
+        SizeProfile {
+            file_size: self.data().len(),
+            memory_size,
+            sections,
+            largest_functions,
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use std::fmt;
+use core::fmt;
 
+use super::format::FormatOptions;
 use super::header::Header;
-use super::{make_u32, make_u64};
-use std::ops::Range;
+use super::mode::Placeholder;
+use super::{make_u32, make_u64, split_u32, split_u64};
+use core::ops::Range;
+use super::prelude::*;
 
 pub struct ProgramHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
@@ -21,7 +24,7 @@ impl<T> ProgramHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
 {
     fn construct(
-        binary: &Vec<u8>,
+        binary: &[u8],
         header: &Header<T>,
         flags: u32,
         entry: &[Range<usize>],
@@ -50,53 +53,155 @@ impl<T> ProgramHeader<T>
             align,
         })
     }
+
+    pub fn program_type(&self) -> ProgramType { self.program_type }
+    pub fn offset(&self) -> T { self.offset }
+    pub fn vaddr(&self) -> T { self.vaddr }
+    pub fn paddr(&self) -> T { self.paddr }
+    pub fn file_size(&self) -> T { self.file_size }
+    pub fn memory_size(&self) -> T { self.memory_size }
+    pub fn flags(&self) -> u32 { self.flags }
+    pub fn align(&self) -> T { self.align }
+}
+
+impl<T> ProgramHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + num::PrimInt
+{
+    /// Bytes past the file contents the loader must zero-fill, such as `.bss`.
+    /// Zero when `file_size` exceeds `memory_size`.
+    pub fn bss_size(&self) -> T { self.memory_size.saturating_sub(self.file_size) }
+
+    /// Checks that `align` is 0 or a power of two and, for `PT_LOAD`, that
+    /// `vaddr` and `offset` are congruent modulo `align` as loaders require.
+    pub fn is_properly_aligned(&self) -> bool {
+        if self.align <= T::one() { return true }
+        if self.align.count_ones() != 1 { return false }
+
+        self.program_type != ProgramType::Load || self.vaddr % self.align == self.offset % self.align
+    }
 }
 
 impl ProgramHeader<u32> {
-    pub fn new(binary: &Vec<u8>, header: &Header<u32>) -> Option<Vec<ProgramHeader<u32>>> {
-        (0..header.ph_num() as usize).map(|index| {
-            let offset = index * header.ph_size() as usize;
-            let entry_point = header.ph_offset() as usize + offset;
+    pub fn new(binary: &[u8], header: &Header<u32>) -> Option<Vec<ProgramHeader<u32>>> {
+        Self::each(binary, header).collect()
+    }
+
+    // every entry, `None` for those that can't be decoded
+    pub(crate) fn each<'a>(binary: &'a [u8], header: &'a Header<u32>) -> impl Iterator<Item = Option<ProgramHeader<u32>>> + 'a {
+        (0..header.ph_num() as usize).map(move |index| {
+            let offset = index.checked_mul(header.ph_size() as usize)?;
+            let entry_point = (header.ph_offset() as usize).checked_add(offset)?;
+            let bytes = binary.get(entry_point..entry_point.checked_add(0x20)?)?;
             let entry = [
-                (entry_point + 0x00..entry_point + 0x04),
-                (entry_point + 0x04..entry_point + 0x08),
-                (entry_point + 0x08..entry_point + 0x0C),
-                (entry_point + 0x0C..entry_point + 0x10),
-                (entry_point + 0x10..entry_point + 0x14),
-                (entry_point + 0x14..entry_point + 0x18),
-                (entry_point + 0x1C..entry_point + 0x20),
+                (0x00..0x04),
+                (0x04..0x08),
+                (0x08..0x0C),
+                (0x0C..0x10),
+                (0x10..0x14),
+                (0x14..0x18),
+                (0x1C..0x20),
             ];
 
-            let flags = make_u32(&binary[entry_point + 0x18..entry_point + 0x1C], header.is_little());
+            let flags = make_u32(&bytes[0x18..0x1C], header.is_little());
 
-            Self::construct(binary, header, flags, &entry, make_u32)
-        }).collect::<Option<Vec<_>>>()
+            Self::construct(bytes, header, flags, &entry, make_u32)
+        })
+    }
+
+    pub fn to_bytes(&self, is_little: bool) -> Vec<u8> {
+        let fields = [
+            split_u32(self.program_type.raw(), is_little),
+            split_u32(self.offset, is_little),
+            split_u32(self.vaddr, is_little),
+            split_u32(self.paddr, is_little),
+            split_u32(self.file_size, is_little),
+            split_u32(self.memory_size, is_little),
+            split_u32(self.flags, is_little),
+            split_u32(self.align, is_little),
+        ];
+
+        fields.concat()
     }
 }
 
 impl ProgramHeader<u64> {
-    pub fn new(binary: &Vec<u8>, header: &Header<u64>) -> Option<Vec<ProgramHeader<u64>>> {
-        (0..header.ph_num() as usize).map(|index| {
-            let offset = index * header.ph_size() as usize;
-            let entry_point = header.ph_offset() as usize + offset;
+    pub fn new(binary: &[u8], header: &Header<u64>) -> Option<Vec<ProgramHeader<u64>>> {
+        Self::each(binary, header).collect()
+    }
+
+    // every entry, `None` for those that can't be decoded
+    pub(crate) fn each<'a>(binary: &'a [u8], header: &'a Header<u64>) -> impl Iterator<Item = Option<ProgramHeader<u64>>> + 'a {
+        (0..header.ph_num() as usize).map(move |index| {
+            let offset = index.checked_mul(header.ph_size() as usize)?;
+            let entry_point = (header.ph_offset() as usize).checked_add(offset)?;
+            let bytes = binary.get(entry_point..entry_point.checked_add(0x38)?)?;
             let entry = [
-                (entry_point + 0x00..entry_point + 0x04),
-                (entry_point + 0x08..entry_point + 0x10),
-                (entry_point + 0x10..entry_point + 0x18),
-                (entry_point + 0x18..entry_point + 0x20),
-                (entry_point + 0x20..entry_point + 0x28),
-                (entry_point + 0x28..entry_point + 0x30),
-                (entry_point + 0x30..entry_point + 0x38),
+                (0x00..0x04),
+                (0x08..0x10),
+                (0x10..0x18),
+                (0x18..0x20),
+                (0x20..0x28),
+                (0x28..0x30),
+                (0x30..0x38),
             ];
 
-            let flags = make_u32(&binary[entry_point + 0x04..entry_point + 0x08], header.is_little());
+            let flags = make_u32(&bytes[0x04..0x08], header.is_little());
 
-            Self::construct(binary, header, flags, &entry, make_u64)
-        }).collect()
+            Self::construct(bytes, header, flags, &entry, make_u64)
+        })
+    }
+
+    pub fn to_bytes(&self, is_little: bool) -> Vec<u8> {
+        let fields = [
+            split_u32(self.program_type.raw(), is_little),
+            split_u32(self.flags, is_little),
+            split_u64(self.offset, is_little),
+            split_u64(self.vaddr, is_little),
+            split_u64(self.paddr, is_little),
+            split_u64(self.file_size, is_little),
+            split_u64(self.memory_size, is_little),
+            split_u64(self.align, is_little),
+        ];
+
+        fields.concat()
     }
 }
 
-#[derive(Debug)]
+/// A `PT_LOAD` segment reduced to the ranges a loader works with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadSegment<T> {
+    pub file_range: Range<T>,
+    pub mem_range: Range<T>,
+    pub flags: SegmentFlags,
+    pub align: T,
+}
+
+/// The `PT_TLS` segment: the image every thread's TLS block is initialized from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TlsTemplate<T> {
+    /// File offset of the initialized data (`.tdata`).
+    pub offset: T,
+    pub vaddr: T,
+    /// Size of `.tdata`, the bytes copied into each block.
+    pub file_size: T,
+    /// Size of the whole block: `.tdata` followed by the zero-filled `.tbss`.
+    pub memory_size: T,
+    pub align: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentFlags(u32);
+
+impl SegmentFlags {
+    pub fn new(value: u32) -> SegmentFlags { SegmentFlags(value) }
+
+    pub fn raw(&self) -> u32 { self.0 }
+    pub fn readable(&self) -> bool { self.0 & 0b100 != 0 }
+    pub fn writable(&self) -> bool { self.0 & 0b010 != 0 }
+    pub fn executable(&self) -> bool { self.0 & 0b001 != 0 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProgramType {
     Null,
     Load,
@@ -106,14 +211,18 @@ pub enum ProgramType {
     Shlib,
     Phdr,
     Tls,
-    Loos,
-    Hios,
-    Loproc,
-    Hiproc,
+    GnuEhFrame,
+    GnuStack,
+    GnuRelro,
+    GnuProperty,
+    Loos(u32),
+    Loproc(u32),
 }
 
 impl ProgramType {
     pub fn new(value: u32) -> Option<ProgramType> {
+        let range_check = |value: u32, base: u32| { value >= base && value < base + 0x1000_0000 };
+
         let t = match value {
             0x0000_0000 => ProgramType::Null,
             0x0000_0001 => ProgramType::Load,
@@ -123,34 +232,67 @@ impl ProgramType {
             0x0000_0005 => ProgramType::Shlib,
             0x0000_0006 => ProgramType::Phdr,
             0x0000_0007 => ProgramType::Tls,
-            0x6000_0000 => ProgramType::Loos,
-            0x6FFF_FFFF => ProgramType::Hios,
-            0x7000_0000 => ProgramType::Loproc,
-            0x7FFF_FFFF => ProgramType::Hiproc,
+            0x6474_E550 => ProgramType::GnuEhFrame,
+            0x6474_E551 => ProgramType::GnuStack,
+            0x6474_E552 => ProgramType::GnuRelro,
+            0x6474_E553 => ProgramType::GnuProperty,
+            value if range_check(value, 0x6000_0000) => ProgramType::Loos(value - 0x6000_0000),
+            value if range_check(value, 0x7000_0000) => ProgramType::Loproc(value - 0x7000_0000),
             _           => return None,
         };
 
         Some(t)
     }
+
+    /// The `p_type` value, the inverse of `new`.
+    pub fn raw(&self) -> u32 {
+        match *self {
+            ProgramType::Null        => 0x0000_0000,
+            ProgramType::Load        => 0x0000_0001,
+            ProgramType::Dynamic     => 0x0000_0002,
+            ProgramType::Interp      => 0x0000_0003,
+            ProgramType::Note        => 0x0000_0004,
+            ProgramType::Shlib       => 0x0000_0005,
+            ProgramType::Phdr        => 0x0000_0006,
+            ProgramType::Tls         => 0x0000_0007,
+            ProgramType::GnuEhFrame  => 0x6474_E550,
+            ProgramType::GnuStack    => 0x6474_E551,
+            ProgramType::GnuRelro    => 0x6474_E552,
+            ProgramType::GnuProperty => 0x6474_E553,
+            ProgramType::Loos(v)     => 0x6000_0000 + v,
+            ProgramType::Loproc(v)   => 0x7000_0000 + v,
+        }
+    }
 }
 
-impl<T> fmt::Display for ProgramHeader<T>
+impl<T> ProgramHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// Formats the header like `Display`, with the hex width and flag style from `options`.
+    pub fn fmt_with(&self, options: &FormatOptions) -> String {
+        let width = options.hex_width.digits::<T>();
         let program_type = format!("Type:          {:?}", self.program_type);
-        let offset       = format!("Offset:        0x{:016x}", self.offset);
-        let vaddr        = format!("Virtual Addr:  0x{:016x}", self.vaddr);
-        let paddr        = format!("Physical Addr: 0x{:016x}", self.paddr);
-        let file_size    = format!("File Size:     0x{:016x}", self.file_size);
-        let memory_size  = format!("Memory Size:   0x{:016x}", self.memory_size);
+        let offset       = format!("Offset:        0x{:01$x}", self.offset, width);
+        let vaddr        = format!("Virtual Addr:  0x{:01$x}", self.vaddr, width);
+        let paddr        = format!("Physical Addr: 0x{:01$x}", self.paddr, width);
+        let file_size    = format!("File Size:     0x{:01$x}", self.file_size, width);
+        let memory_size  = format!("Memory Size:   0x{:01$x}", self.memory_size, width);
         let align        = format!("Align:         0x{:x}", self.align);
-        let r = if (self.flags & 0b100) > 0 {"R"} else {" "};
-        let w = if (self.flags & 0b010) > 0 {"W"} else {" "};
-        let x = if (self.flags & 0b001) > 0 {"E"} else {" "};
-        let flags        = format!("Flags:         {}{}{}", r, w, x);
+        let flags = if options.show_flags_long {
+            let names = [(0b100, "READ"), (0b010, "WRITE"), (0b001, "EXEC")];
+            let names = names.iter()
+                .filter(|(mask, _)| self.flags & mask > 0)
+                .map(|(_, name)| *name)
+                .collect::<Vec<_>>();
+            format!("Flags:         {}", names.join(", "))
+        } else {
+            let r = if (self.flags & 0b100) > 0 {"R"} else {" "};
+            let w = if (self.flags & 0b010) > 0 {"W"} else {" "};
+            let x = if (self.flags & 0b001) > 0 {"E"} else {" "};
+            format!("Flags:         {}{}{}", r, w, x)
+        };
 
-        let formatted_string = format!(
+        format!(
             "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
             program_type,
             offset,
@@ -160,8 +302,31 @@ impl<T> fmt::Display for ProgramHeader<T>
             memory_size,
             flags,
             align,
-        );
+        )
+    }
+}
 
-        write!(f, "{}", formatted_string)
+impl<T> fmt::Display for ProgramHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fmt_with(&FormatOptions::default()))
+    }
+}
+
+impl<T> Placeholder for ProgramHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + num::Zero
+{
+    fn placeholder() -> Self {
+        ProgramHeader {
+            program_type: ProgramType::Null,
+            offset: T::zero(),
+            vaddr: T::zero(),
+            paddr: T::zero(),
+            file_size: T::zero(),
+            memory_size: T::zero(),
+            flags: 0,
+            align: T::zero(),
+        }
     }
 }
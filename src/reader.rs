@@ -0,0 +1,60 @@
+use super::{Class, ElfError};
+use super::{make_u16, make_u32, make_u64};
+
+/// A cursor over ELF data that decodes integers in the file's byte order.
+///
+/// Every read advances the position and fails with `ElfError::Truncated`
+/// instead of panicking when the data runs out.
+pub struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    little: bool,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8], little: bool) -> Reader<'a> {
+        Reader { data, pos: 0, little }
+    }
+
+    pub fn position(&self) -> usize { self.pos }
+
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos;
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], ElfError> {
+        let truncated = ElfError::Truncated { needed: len, offset: self.pos, len: self.data.len() };
+        let end = match self.pos.checked_add(len) {
+            Some(end) if end <= self.data.len() => end,
+            _ => return Err(truncated),
+        };
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+
+        Ok(bytes)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, ElfError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, ElfError> {
+        Ok(make_u16(self.bytes(2)?, self.little))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, ElfError> {
+        Ok(make_u32(self.bytes(4)?, self.little))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, ElfError> {
+        Ok(make_u64(self.bytes(8)?, self.little))
+    }
+
+    /// Reads an address-sized word: 4 bytes for `ELF32`, 8 for `ELF64`.
+    pub fn word(&mut self, class: Class) -> Result<u64, ElfError> {
+        match class {
+            Class::ELF32 => self.u32().map(|value| value as u64),
+            Class::ELF64 => self.u64(),
+        }
+    }
+}
@@ -0,0 +1,437 @@
+use core::fmt;
+use core::mem;
+
+use num::cast;
+
+use super::{DynamicTag, ELF, ISA, SectionHeader, SectionType, SymbolType};
+use super::{make_u32, make_u64};
+use super::prelude::*;
+
+/// An entry of a `SHT_REL` or `SHT_RELA` section.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Relocation<T> {
+    offset: T,
+    info: T,
+    addend: Option<i64>,
+}
+
+impl<T> Relocation<T>
+    where T: Copy + num::PrimInt
+{
+    pub fn offset(&self) -> T { self.offset }
+    pub fn info(&self) -> T { self.info }
+    /// The explicit addend of `SHT_RELA` entries; `SHT_REL` entries keep it
+    /// in the relocated field and have `None`.
+    pub fn addend(&self) -> Option<i64> { self.addend }
+
+    /// Index into the symbol table named by the section's `sh_link`.
+    pub fn symbol_index(&self) -> usize {
+        let info = self.info.to_u64().unwrap_or(0);
+        if mem::size_of::<T>() == 4 { (info >> 8) as usize } else { (info >> 32) as usize }
+    }
+
+    /// The processor-specific relocation type, e.g. `R_X86_64_GLOB_DAT`.
+    pub fn rel_type(&self) -> u32 {
+        let info = self.info.to_u64().unwrap_or(0);
+        if mem::size_of::<T>() == 4 { (info & 0xFF) as u32 } else { (info & 0xFFFF_FFFF) as u32 }
+    }
+}
+
+/// A `DT_JMPREL` relocation: the GOT slot a PLT stub jumps through, and
+/// the function the dynamic linker resolves into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PltEntry<T> {
+    relocation: Relocation<T>,
+    symbol: Option<String>,
+}
+
+impl<T> PltEntry<T>
+    where T: Copy + num::PrimInt
+{
+    pub fn relocation(&self) -> &Relocation<T> { &self.relocation }
+    /// Address of the GOT slot the relocation fills in.
+    pub fn got_address(&self) -> T { self.relocation.offset }
+    /// The `.dynsym` name of the target; `None` for symbol-less relocations
+    /// such as `R_X86_64_IRELATIVE`.
+    pub fn symbol(&self) -> Option<String> { self.symbol.clone() }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    /// Decodes the addresses relocated by `SHT_RELR` sections (`.relr.dyn`).
+    ///
+    /// An even entry is an address to relocate. An odd entry is a bitmap whose
+    /// bits, after the marker bit, flag the words following the last address.
+    pub fn relr_relocations(&self) -> Vec<T> {
+        let is_little = self.header.is_little();
+        let word_size = mem::size_of::<T>();
+        let word_bits = word_size * 8;
+
+        let entries = self.section_headers
+            .iter()
+            .filter(|sh| sh.section_type() == SectionType::RelR)
+            .filter_map(|sh| sh.file_range(self.data().len()))
+            .flat_map(|range| range.step_by(word_size))
+            .filter_map(|offset| {
+                let bytes = self.data().get(offset..offset + word_size)?;
+                if word_size == 4 { Some(make_u32(bytes, is_little) as u64) }
+                else { Some(make_u64(bytes, is_little)) }
+            });
+
+        let mut addresses = Vec::new();
+        let mut next = 0u64;
+        for entry in entries {
+            if entry & 1 == 0 {
+                addresses.push(entry);
+                next = entry.wrapping_add(word_size as u64);
+            } else {
+                let bitmap = entry >> 1;
+                addresses.extend((0..word_bits as u64 - 1)
+                    .filter(|bit| bitmap & (1 << bit) != 0)
+                    .map(|bit| next.wrapping_add(bit * word_size as u64)));
+                next = next.wrapping_add((word_bits as u64 - 1) * word_size as u64);
+            }
+        }
+
+        addresses.into_iter().filter_map(num::cast).collect()
+    }
+
+    /// Decodes the entries of the `SHT_REL` or `SHT_RELA` section `section`.
+    /// `None` for other section types or entries outside the file.
+    pub fn relocations(&self, section: &SectionHeader<T>) -> Option<Vec<Relocation<T>>> {
+        let is_rela = match section.section_type() {
+            SectionType::Rel => false,
+            SectionType::Rela => true,
+            _ => return None,
+        };
+
+        let is_little = self.header.is_little();
+        let word_size = mem::size_of::<T>();
+        let entry_size = if is_rela { word_size * 3 } else { word_size * 2 };
+        let range = section.file_range(self.data().len())?;
+        let read_word = |offset: usize| -> Option<u64> {
+            let bytes = self.data().get(offset..offset.checked_add(word_size)?)?;
+            if word_size == 4 { Some(make_u32(bytes, is_little) as u64) }
+            else { Some(make_u64(bytes, is_little)) }
+        };
+
+        range.step_by(entry_size).take(section.size().as_() / entry_size).map(|start| {
+            let addend = match is_rela {
+                // the addend is signed, so sign-extend 32-bit ones
+                true if word_size == 4 => Some(read_word(start + word_size * 2)? as u32 as i32 as i64),
+                true => Some(read_word(start + word_size * 2)? as i64),
+                false => None,
+            };
+
+            Some(Relocation {
+                offset: num::cast(read_word(start)?)?,
+                info: num::cast(read_word(start + word_size)?)?,
+                addend,
+            })
+        }).collect()
+    }
+
+    /// Lists the relocations `DT_JMPREL` and `DT_PLTRELSZ` describe, each
+    /// paired with the `.dynsym` symbol it binds.
+    ///
+    /// The section is found by address, so it doesn't matter what it's named
+    /// or whether it shares a section with the other dynamic relocations.
+    pub fn plt_entries(&self) -> Vec<PltEntry<T>> {
+        let (jmprel, size) = match (self.dynamic_value(DynamicTag::JmpRel), self.dynamic_value(DynamicTag::PltRelSz)) {
+            (Some(jmprel), Some(size)) => (jmprel.as_(), size.as_()),
+            _ => return Vec::new(),
+        };
+
+        let section = self.section_headers.iter().find(|sh| {
+            let addr = sh.target_addr().as_();
+            matches!(sh.section_type(), SectionType::Rel | SectionType::Rela)
+                && jmprel >= addr && jmprel < addr.saturating_add(sh.size().as_())
+        });
+        let section = match section {
+            Some(section) => section,
+            None => return Vec::new(),
+        };
+
+        let word_size = mem::size_of::<T>();
+        let entry_size = if section.section_type() == SectionType::Rela { word_size * 3 } else { word_size * 2 };
+        let skip = (jmprel - section.target_addr().as_()) / entry_size;
+
+        self.relocations(section).unwrap_or_default().into_iter()
+            .skip(skip)
+            .take(size / entry_size)
+            .map(|relocation| {
+                let symbol = match relocation.symbol_index() {
+                    0 => None,
+                    index => self.dynamic_symbols.get(index).map(|sym| sym.name()),
+                };
+
+                PltEntry { relocation, symbol }
+            })
+            .collect()
+    }
+
+    /// Formats every `SHT_REL` and `SHT_RELA` section the way `readelf -rW`
+    /// prints them, with symbol names and versions resolved through the
+    /// section's `sh_link`.
+    ///
+    /// Relocation types are named for x86-64 and AArch64; others are printed
+    /// as numbers.
+    pub fn format_relocations(&self) -> String {
+        let sections = self.section_headers
+            .iter()
+            .filter_map(|sh| Some((sh, self.relocations(sh)?)))
+            .map(|(sh, relocations)| self.format_relocation_section(sh, &relocations))
+            .collect::<Vec<_>>();
+
+        if sections.is_empty() { "There are no relocations in this file.\n".to_string() }
+        else { sections.join("\n") }
+    }
+
+    fn format_relocation_section(&self, section: &SectionHeader<T>, relocations: &[Relocation<T>]) -> String {
+        let is_rela = section.section_type() == SectionType::Rela;
+        let is_64 = mem::size_of::<T>() == 8;
+        let digits = mem::size_of::<T>() * 2;
+
+        let symtab = section.linked_section(self);
+        let (symbols, versions) = match symtab.map(|sh| sh.section_type()) {
+            Some(SectionType::DynSym) => (&self.dynamic_symbols[..], self.symbol_versions()),
+            Some(SectionType::SymTab) => (&self.symbols[..], Vec::new()),
+            _ => (&[][..], Vec::new()),
+        };
+
+        let mut lines = vec![format!(
+            "Relocation section '{}' at offset 0x{:x} contains {} {}:",
+            section.name(), section.file_offset(), relocations.len(),
+            if relocations.len() == 1 { "entry" } else { "entries" },
+        )];
+        lines.push(match (is_64, is_rela) {
+            (true, _) => "    Offset             Info             Type               Symbol's Value  Symbol's Name + Addend",
+            (false, true) => " Offset     Info    Type                Sym. Value  Symbol's Name + Addend",
+            (false, false) => " Offset     Info    Type                Sym. Value  Symbol's Name",
+        }.to_string());
+
+        for relocation in relocations {
+            let type_name = relocation_type_name(self.header.isa(), relocation.rel_type())
+                .map(String::from)
+                .unwrap_or_else(|| format!("unrecognized: {:x}", relocation.rel_type()));
+            let mut line = format!(
+                "{:0width$x}  {:0width$x} {:<22}",
+                relocation.offset(), relocation.info(), type_name, width = digits,
+            );
+
+            let index = relocation.symbol_index();
+            match symbols.get(index).filter(|_| index != 0) {
+                Some(sym) => {
+                    let name = match sym.sym_type() {
+                        SymbolType::Section if sym.name().is_empty() =>
+                            sym.section_index().and_then(|index| self.section(index)).map(|sh| sh.name()).unwrap_or_default(),
+                        _ => sym.name(),
+                    };
+                    let version = versions.get(index)
+                        .and_then(|v| Some(format!("{}{}", v.separator(), v.version()?)))
+                        .unwrap_or_default();
+
+                    let separator = if is_64 { " " } else { "   " };
+                    line += &format!(" {:0width$x}{}{}{}", sym.value(), separator, name, version, width = digits);
+                    if let Some(addend) = relocation.addend() {
+                        line += &format_addend(addend);
+                    }
+                }
+                // no symbol: only the addend, in the value and name columns
+                None => if let Some(addend) = relocation.addend() {
+                    line += &format!("{:width$}{:x}", "", addend, width = if is_64 { 20 } else { 12 });
+                },
+            }
+
+            lines.push(line);
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn format_addend(addend: i64) -> String {
+    if addend < 0 { format!(" - {:x}", addend.unsigned_abs()) } else { format!(" + {:x}", addend) }
+}
+
+fn relocation_type_name(isa: ISA, rel_type: u32) -> Option<&'static str> {
+    let names = match isa {
+        ISA::x86_64 => X86_64_RELOCATIONS,
+        ISA::AArch64 => AARCH64_RELOCATIONS,
+        _ => return None,
+    };
+
+    names.iter().find(|(value, _)| *value == rel_type).map(|(_, name)| *name)
+}
+
+const X86_64_RELOCATIONS: &[(u32, &str)] = &[
+    (0, "R_X86_64_NONE"),
+    (1, "R_X86_64_64"),
+    (2, "R_X86_64_PC32"),
+    (3, "R_X86_64_GOT32"),
+    (4, "R_X86_64_PLT32"),
+    (5, "R_X86_64_COPY"),
+    (6, "R_X86_64_GLOB_DAT"),
+    (7, "R_X86_64_JUMP_SLOT"),
+    (8, "R_X86_64_RELATIVE"),
+    (9, "R_X86_64_GOTPCREL"),
+    (10, "R_X86_64_32"),
+    (11, "R_X86_64_32S"),
+    (12, "R_X86_64_16"),
+    (13, "R_X86_64_PC16"),
+    (14, "R_X86_64_8"),
+    (15, "R_X86_64_PC8"),
+    (16, "R_X86_64_DTPMOD64"),
+    (17, "R_X86_64_DTPOFF64"),
+    (18, "R_X86_64_TPOFF64"),
+    (19, "R_X86_64_TLSGD"),
+    (20, "R_X86_64_TLSLD"),
+    (21, "R_X86_64_DTPOFF32"),
+    (22, "R_X86_64_GOTTPOFF"),
+    (23, "R_X86_64_TPOFF32"),
+    (24, "R_X86_64_PC64"),
+    (25, "R_X86_64_GOTOFF64"),
+    (26, "R_X86_64_GOTPC32"),
+    (27, "R_X86_64_GOT64"),
+    (28, "R_X86_64_GOTPCREL64"),
+    (29, "R_X86_64_GOTPC64"),
+    (30, "R_X86_64_GOTPLT64"),
+    (31, "R_X86_64_PLTOFF64"),
+    (32, "R_X86_64_SIZE32"),
+    (33, "R_X86_64_SIZE64"),
+    (34, "R_X86_64_GOTPC32_TLSDESC"),
+    (35, "R_X86_64_TLSDESC_CALL"),
+    (36, "R_X86_64_TLSDESC"),
+    (37, "R_X86_64_IRELATIVE"),
+    (38, "R_X86_64_RELATIVE64"),
+    (41, "R_X86_64_GOTPCRELX"),
+    (42, "R_X86_64_REX_GOTPCRELX"),
+];
+
+const AARCH64_RELOCATIONS: &[(u32, &str)] = &[
+    (0, "R_AARCH64_NONE"),
+    (257, "R_AARCH64_ABS64"),
+    (258, "R_AARCH64_ABS32"),
+    (259, "R_AARCH64_ABS16"),
+    (260, "R_AARCH64_PREL64"),
+    (261, "R_AARCH64_PREL32"),
+    (262, "R_AARCH64_PREL16"),
+    (263, "R_AARCH64_MOVW_UABS_G0"),
+    (264, "R_AARCH64_MOVW_UABS_G0_NC"),
+    (265, "R_AARCH64_MOVW_UABS_G1"),
+    (266, "R_AARCH64_MOVW_UABS_G1_NC"),
+    (267, "R_AARCH64_MOVW_UABS_G2"),
+    (268, "R_AARCH64_MOVW_UABS_G2_NC"),
+    (269, "R_AARCH64_MOVW_UABS_G3"),
+    (270, "R_AARCH64_MOVW_SABS_G0"),
+    (271, "R_AARCH64_MOVW_SABS_G1"),
+    (272, "R_AARCH64_MOVW_SABS_G2"),
+    (273, "R_AARCH64_LD_PREL_LO19"),
+    (274, "R_AARCH64_ADR_PREL_LO21"),
+    (275, "R_AARCH64_ADR_PREL_PG_HI21"),
+    (276, "R_AARCH64_ADR_PREL_PG_HI21_NC"),
+    (277, "R_AARCH64_ADD_ABS_LO12_NC"),
+    (278, "R_AARCH64_LDST8_ABS_LO12_NC"),
+    (279, "R_AARCH64_TSTBR14"),
+    (280, "R_AARCH64_CONDBR19"),
+    (282, "R_AARCH64_JUMP26"),
+    (283, "R_AARCH64_CALL26"),
+    (284, "R_AARCH64_LDST16_ABS_LO12_NC"),
+    (285, "R_AARCH64_LDST32_ABS_LO12_NC"),
+    (286, "R_AARCH64_LDST64_ABS_LO12_NC"),
+    (287, "R_AARCH64_MOVW_PREL_G0"),
+    (288, "R_AARCH64_MOVW_PREL_G0_NC"),
+    (289, "R_AARCH64_MOVW_PREL_G1"),
+    (290, "R_AARCH64_MOVW_PREL_G1_NC"),
+    (291, "R_AARCH64_MOVW_PREL_G2"),
+    (292, "R_AARCH64_MOVW_PREL_G2_NC"),
+    (293, "R_AARCH64_MOVW_PREL_G3"),
+    (299, "R_AARCH64_LDST128_ABS_LO12_NC"),
+    (300, "R_AARCH64_MOVW_GOTOFF_G0"),
+    (301, "R_AARCH64_MOVW_GOTOFF_G0_NC"),
+    (302, "R_AARCH64_MOVW_GOTOFF_G1"),
+    (303, "R_AARCH64_MOVW_GOTOFF_G1_NC"),
+    (304, "R_AARCH64_MOVW_GOTOFF_G2"),
+    (305, "R_AARCH64_MOVW_GOTOFF_G2_NC"),
+    (306, "R_AARCH64_MOVW_GOTOFF_G3"),
+    (307, "R_AARCH64_GOTREL64"),
+    (308, "R_AARCH64_GOTREL32"),
+    (309, "R_AARCH64_GOT_LD_PREL19"),
+    (310, "R_AARCH64_LD64_GOTOFF_LO15"),
+    (311, "R_AARCH64_ADR_GOT_PAGE"),
+    (312, "R_AARCH64_LD64_GOT_LO12_NC"),
+    (313, "R_AARCH64_LD64_GOTPAGE_LO15"),
+    (512, "R_AARCH64_TLSGD_ADR_PREL21"),
+    (513, "R_AARCH64_TLSGD_ADR_PAGE21"),
+    (514, "R_AARCH64_TLSGD_ADD_LO12_NC"),
+    (515, "R_AARCH64_TLSGD_MOVW_G1"),
+    (516, "R_AARCH64_TLSGD_MOVW_G0_NC"),
+    (517, "R_AARCH64_TLSLD_ADR_PREL21"),
+    (518, "R_AARCH64_TLSLD_ADR_PAGE21"),
+    (519, "R_AARCH64_TLSLD_ADD_LO12_NC"),
+    (520, "R_AARCH64_TLSLD_MOVW_G1"),
+    (521, "R_AARCH64_TLSLD_MOVW_G0_NC"),
+    (522, "R_AARCH64_TLSLD_LD_PREL19"),
+    (523, "R_AARCH64_TLSLD_MOVW_DTPREL_G2"),
+    (524, "R_AARCH64_TLSLD_MOVW_DTPREL_G1"),
+    (525, "R_AARCH64_TLSLD_MOVW_DTPREL_G1_NC"),
+    (526, "R_AARCH64_TLSLD_MOVW_DTPREL_G0"),
+    (527, "R_AARCH64_TLSLD_MOVW_DTPREL_G0_NC"),
+    (528, "R_AARCH64_TLSLD_ADD_DTPREL_HI12"),
+    (529, "R_AARCH64_TLSLD_ADD_DTPREL_LO12"),
+    (530, "R_AARCH64_TLSLD_ADD_DTPREL_LO12_NC"),
+    (531, "R_AARCH64_TLSLD_LDST8_DTPREL_LO12"),
+    (532, "R_AARCH64_TLSLD_LDST8_DTPREL_LO12_NC"),
+    (533, "R_AARCH64_TLSLD_LDST16_DTPREL_LO12"),
+    (534, "R_AARCH64_TLSLD_LDST16_DTPREL_LO12_NC"),
+    (535, "R_AARCH64_TLSLD_LDST32_DTPREL_LO12"),
+    (536, "R_AARCH64_TLSLD_LDST32_DTPREL_LO12_NC"),
+    (537, "R_AARCH64_TLSLD_LDST64_DTPREL_LO12"),
+    (538, "R_AARCH64_TLSLD_LDST64_DTPREL_LO12_NC"),
+    (539, "R_AARCH64_TLSIE_MOVW_GOTTPREL_G1"),
+    (540, "R_AARCH64_TLSIE_MOVW_GOTTPREL_G0_NC"),
+    (541, "R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21"),
+    (542, "R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC"),
+    (543, "R_AARCH64_TLSIE_LD_GOTTPREL_PREL19"),
+    (544, "R_AARCH64_TLSLE_MOVW_TPREL_G2"),
+    (545, "R_AARCH64_TLSLE_MOVW_TPREL_G1"),
+    (546, "R_AARCH64_TLSLE_MOVW_TPREL_G1_NC"),
+    (547, "R_AARCH64_TLSLE_MOVW_TPREL_G0"),
+    (548, "R_AARCH64_TLSLE_MOVW_TPREL_G0_NC"),
+    (549, "R_AARCH64_TLSLE_ADD_TPREL_HI12"),
+    (550, "R_AARCH64_TLSLE_ADD_TPREL_LO12"),
+    (551, "R_AARCH64_TLSLE_ADD_TPREL_LO12_NC"),
+    (552, "R_AARCH64_TLSLE_LDST8_TPREL_LO12"),
+    (553, "R_AARCH64_TLSLE_LDST8_TPREL_LO12_NC"),
+    (554, "R_AARCH64_TLSLE_LDST16_TPREL_LO12"),
+    (555, "R_AARCH64_TLSLE_LDST16_TPREL_LO12_NC"),
+    (556, "R_AARCH64_TLSLE_LDST32_TPREL_LO12"),
+    (557, "R_AARCH64_TLSLE_LDST32_TPREL_LO12_NC"),
+    (558, "R_AARCH64_TLSLE_LDST64_TPREL_LO12"),
+    (559, "R_AARCH64_TLSLE_LDST64_TPREL_LO12_NC"),
+    (560, "R_AARCH64_TLSDESC_LD_PREL19"),
+    (561, "R_AARCH64_TLSDESC_ADR_PREL21"),
+    (562, "R_AARCH64_TLSDESC_ADR_PAGE21"),
+    (563, "R_AARCH64_TLSDESC_LD64_LO12"),
+    (564, "R_AARCH64_TLSDESC_ADD_LO12"),
+    (565, "R_AARCH64_TLSDESC_OFF_G1"),
+    (566, "R_AARCH64_TLSDESC_OFF_G0_NC"),
+    (567, "R_AARCH64_TLSDESC_LDR"),
+    (568, "R_AARCH64_TLSDESC_ADD"),
+    (569, "R_AARCH64_TLSDESC_CALL"),
+    (570, "R_AARCH64_TLSLE_LDST128_TPREL_LO12"),
+    (571, "R_AARCH64_TLSLE_LDST128_TPREL_LO12_NC"),
+    (572, "R_AARCH64_TLSLD_LDST128_DTPREL_LO12"),
+    (573, "R_AARCH64_TLSLD_LDST128_DTPREL_LO12_NC"),
+    (1024, "R_AARCH64_COPY"),
+    (1025, "R_AARCH64_GLOB_DAT"),
+    (1026, "R_AARCH64_JUMP_SLOT"),
+    (1027, "R_AARCH64_RELATIVE"),
+    (1028, "R_AARCH64_TLS_DTPMOD"),
+    (1029, "R_AARCH64_TLS_DTPREL"),
+    (1030, "R_AARCH64_TLS_TPREL"),
+    (1031, "R_AARCH64_TLSDESC"),
+    (1032, "R_AARCH64_IRELATIVE"),
+];
@@ -1,12 +1,17 @@
 extern crate num;
 
-use std::fmt;
-use std::ops;
+use core::fmt;
+use core::ops;
 
 use num::cast;
 
-use super::Header;
-use super::{make_u32, make_u64};
+use super::{ELF, ElfError, Header};
+use super::format::FormatOptions;
+use super::mode::Placeholder;
+use super::sections;
+use super::strtab::StringTable;
+use super::{make_u32, make_u64, split_u32, split_u64, truncated_table};
+use super::prelude::*;
 
 pub struct SectionHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
@@ -18,30 +23,69 @@ pub struct SectionHeader<T>
 impl<T> SectionHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
 {
-    fn construct(binary: &Vec<u8>, header: &Header<T>, inner_headers: Vec<InnerSectionHeader<T>>) -> Option<Vec<SectionHeader<T>>> {
-        let shstrtab = match inner_headers.last() {
-            Some(section) => section,
-            None => return Some(Vec::new()),
-        };
+    fn construct(binary: &[u8], header: &Header<T>, inner_headers: Vec<InnerSectionHeader<T>>) -> Option<Vec<SectionHeader<T>>> {
+        if inner_headers.is_empty() { return Some(Vec::new()) }
+        let shstrtab = InnerSectionHeader::shstrtab(header, |index| inner_headers.get(index).cloned())?;
+        let strings = StringTable::indexed(binary, shstrtab.offset.as_(), shstrtab.size.as_());
+
+        inner_headers.into_iter().map(|inner| Self::named(&strings, inner)).collect::<Option<Vec<_>>>()
+    }
+
+    fn construct_lazy<'a>(
+        binary: &'a [u8],
+        header: &'a Header<T>,
+        entry: impl Fn(usize) -> Option<InnerSectionHeader<T>> + 'a,
+    ) -> impl Iterator<Item = SectionHeader<T>> + 'a {
+        let count = InnerSectionHeader::count(header, &entry).unwrap_or(0);
+
+        Self::construct_each(binary, header, count, entry).map_while(|section| section)
+    }
+
+    // the first `count` entries, `None` for those that can't be decoded or named
+    fn construct_each<'a>(
+        binary: &'a [u8],
+        header: &'a Header<T>,
+        count: usize,
+        entry: impl Fn(usize) -> Option<InnerSectionHeader<T>> + 'a,
+    ) -> impl Iterator<Item = Option<SectionHeader<T>>> + 'a {
+        let strings = InnerSectionHeader::shstrtab(header, &entry)
+            .map(|shstrtab| StringTable::indexed(binary, shstrtab.offset.as_(), shstrtab.size.as_()));
+
+        (0..count).map(move |index| Self::named(strings.as_ref()?, entry(index)?))
+    }
 
-        let section_entry = shstrtab.offset.as_();
+    // `Truncated` when the table, or a name it points at, lies past the end of `binary`
+    fn truncation_of(
+        binary: &[u8],
+        header: &Header<T>,
+        entry_len: usize,
+        entry: impl Fn(usize) -> Option<InnerSectionHeader<T>>,
+    ) -> Option<ElfError> {
+        // an unreadable section 0 can't give the extended count, so check it alone
+        let count = InnerSectionHeader::count(header, &entry).unwrap_or(1);
+        let table = truncated_table(binary, header.sh_offset().as_(), count, header.sh_size() as usize, entry_len);
+        if table.is_some() { return table }
+
+        let strtab = InnerSectionHeader::shstrtab(header, &entry)?.offset.as_();
+        (0..count).filter_map(&entry).find_map(|inner| match inner.name_offset.checked_add(strtab) {
+            Some(name) if name < binary.len() => None,
+            name => Some(ElfError::Truncated { needed: 1, offset: name.unwrap_or(usize::MAX), len: binary.len() }),
+        })
+    }
 
-        inner_headers.iter().map(|inner| {
-            let name_entry = inner.name_offset + section_entry;
-            let end_of_string = binary[name_entry..].iter().position(|&bin| { bin == 0 })?;
-            let name = match std::str::from_utf8(&binary[name_entry..name_entry + end_of_string]) {
-                Ok(s) => String::from(s),
-                Err(_) => return None,
-            };
+    /// Resolves the name of `inner` in the section name table `strings`.
+    fn named(strings: &StringTable<'_>, inner: InnerSectionHeader<T>) -> Option<SectionHeader<T>> {
+        let name = match core::str::from_utf8(strings.get(inner.name_offset)?) {
+            Ok(s) => String::from(s),
+            Err(_) => return None,
+        };
 
-            Some(SectionHeader{
-                name,
-                inner: inner.clone(),
-            })
-        }).collect::<Option<Vec<_>>>()
+        Some(SectionHeader { name, inner })
     }
 
     pub fn name(&self) -> String { self.name.clone() }
+    pub(crate) fn name_str(&self) -> &str { &self.name }
+    pub fn name_offset(&self) -> usize { self.inner.name_offset }
     pub fn section_type(&self) -> SectionType { self.inner.section_type }
     pub fn flags(&self) -> T { self.inner.flags }
     pub fn target_addr(&self) -> T { self.inner.addr }
@@ -51,33 +95,163 @@ impl<T> SectionHeader<T>
     pub fn info(&self) -> u32 { self.inner.info }
     pub fn addr_align(&self) -> T { self.inner.addr_align }
     pub fn entry_size(&self) -> T { self.inner.entry_size }
+
+    pub fn is_writable(&self) -> bool {
+        self.inner.flags.as_() & 0x0000_0001 != 0
+    }
+
+    pub fn is_alloc(&self) -> bool {
+        self.inner.flags.as_() & 0x0000_0002 != 0
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.inner.flags.as_() & 0x0000_0004 != 0
+    }
+
+    /// Reports whether `SHF_COMPRESSED` is set, i.e. the contents start with
+    /// an `Elf_Chdr` compression header.
+    pub fn is_compressed(&self) -> bool {
+        self.inner.flags.as_() & 0x0000_0800 != 0
+    }
+
+    /// Buckets the section by its flags for size reports. Allocated `NoBits`
+    /// sections are `Bss` even though they are also writable data.
+    pub fn category(&self) -> SectionCategory {
+        if !self.is_alloc() { SectionCategory::Metadata }
+        else if self.inner.section_type == SectionType::NoBits { SectionCategory::Bss }
+        else if self.is_executable() { SectionCategory::Code }
+        else if self.is_writable() { SectionCategory::Data }
+        else { SectionCategory::ReadOnlyData }
+    }
+
+    /// Returns the bytes `[offset, offset + size)` the section occupies in a
+    /// file of `file_len` bytes, or `None` if they don't fit (an overflowing
+    /// end counts as not fitting). `NoBits` sections occupy nothing and get `None`.
+    pub fn file_range(&self, file_len: usize) -> Option<ops::Range<usize>> {
+        if self.inner.section_type == SectionType::NoBits { return None }
+
+        let start = self.inner.offset.as_();
+        let end = start.checked_add(self.inner.size.as_())?;
+        if end > file_len { return None }
+
+        Some(start..end)
+    }
+
+    /// Classifies the section by its conventional name; `None` for names
+    /// outside `sections`.
+    pub fn well_known(&self) -> Option<WellKnownSection> {
+        WellKnownSection::new(&self.name)
+    }
+
+    /// Returns the section `sh_link` refers to, such as the string table of
+    /// a symbol table or the symbol table of a relocation section.
+    /// `None` when `sh_link` is 0 or out of range.
+    pub fn linked_section<'a, D: AsRef<[u8]>>(&self, elf: &'a ELF<T, D>) -> Option<&'a SectionHeader<T>> {
+        match self.inner.link {
+            0 => None,
+            link => elf.section(link as usize),
+        }
+    }
 }
 
 impl SectionHeader<u32> {
-    pub fn new(binary: &Vec<u8>, header: &Header<u32>) -> Option<Vec<SectionHeader<u32>>> {
+    pub fn new(binary: &[u8], header: &Header<u32>) -> Option<Vec<SectionHeader<u32>>> {
         let ih = InnerSectionHeader::<u32>::new(binary, header)?;
         Self::construct(binary, header, ih)
     }
+
+    /// Like `new`, but decodes each entry only when the iterator reaches it.
+    /// Iteration stops at the first entry that can't be decoded.
+    pub fn lazy<'a>(binary: &'a [u8], header: &'a Header<u32>) -> impl Iterator<Item = SectionHeader<u32>> + 'a {
+        Self::construct_lazy(binary, header, move |index| InnerSectionHeader::<u32>::entry(binary, header, index))
+    }
+
+    // every entry, `None` for those that can't be decoded; `None` overall
+    // when the count itself is unusable
+    pub(crate) fn each<'a>(binary: &'a [u8], header: &'a Header<u32>) -> Option<impl Iterator<Item = Option<SectionHeader<u32>>> + 'a> {
+        let count = InnerSectionHeader::<u32>::checked_count(binary, header)?;
+        Some(Self::construct_each(binary, header, count, move |index| InnerSectionHeader::<u32>::entry(binary, header, index)))
+    }
+
+    // why `each` or its entries failed, when it's because something lies past the end of `binary`
+    pub(crate) fn truncation(binary: &[u8], header: &Header<u32>) -> Option<ElfError> {
+        Self::truncation_of(binary, header, 0x28, |index| InnerSectionHeader::<u32>::entry(binary, header, index))
+    }
+
+    pub fn to_bytes(&self, is_little: bool) -> Vec<u8> {
+        self.inner.serialize(is_little, split_u32)
+    }
 }
 
 impl SectionHeader<u64> {
-    pub fn new(binary: &Vec<u8>, header: &Header<u64>) -> Option<Vec<SectionHeader<u64>>> {
+    pub fn new(binary: &[u8], header: &Header<u64>) -> Option<Vec<SectionHeader<u64>>> {
         Self::construct(binary, header, InnerSectionHeader::<u64>::new(binary, header)?)
     }
+
+    /// Like `new`, but decodes each entry only when the iterator reaches it.
+    /// Iteration stops at the first entry that can't be decoded.
+    pub fn lazy<'a>(binary: &'a [u8], header: &'a Header<u64>) -> impl Iterator<Item = SectionHeader<u64>> + 'a {
+        Self::construct_lazy(binary, header, move |index| InnerSectionHeader::<u64>::entry(binary, header, index))
+    }
+
+    // every entry, `None` for those that can't be decoded; `None` overall
+    // when the count itself is unusable
+    pub(crate) fn each<'a>(binary: &'a [u8], header: &'a Header<u64>) -> Option<impl Iterator<Item = Option<SectionHeader<u64>>> + 'a> {
+        let count = InnerSectionHeader::<u64>::checked_count(binary, header)?;
+        Some(Self::construct_each(binary, header, count, move |index| InnerSectionHeader::<u64>::entry(binary, header, index)))
+    }
+
+    // why `each` or its entries failed, when it's because something lies past the end of `binary`
+    pub(crate) fn truncation(binary: &[u8], header: &Header<u64>) -> Option<ElfError> {
+        Self::truncation_of(binary, header, 0x40, |index| InnerSectionHeader::<u64>::entry(binary, header, index))
+    }
+
+    pub fn to_bytes(&self, is_little: bool) -> Vec<u8> {
+        self.inner.serialize(is_little, split_u64)
+    }
+}
+
+impl<T> SectionHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    /// Formats the header like `Display`, with the hex width and flag style from `options`.
+    pub fn fmt_with(&self, options: &FormatOptions) -> String {
+        let inner = self.inner.fmt_with(options)
+            .split('\n')
+            .map(|line| if line.trim().is_empty() { String::new() } else { format!("    {}", line) })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        format!("{}\n{}", self.name, inner)
+    }
 }
 
 impl<T> fmt::Display for SectionHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let inner = self.inner.to_string();
-        let inner = inner
-            .split("\n")
-            .map(|line| { format!("    {}", line)})
-            .collect::<Vec<String>>()
-            .connect("\n");
+        write!(f, "{}", self.fmt_with(&FormatOptions::default()))
+    }
+}
 
-        write!(f, "{}\n{}", self.name, inner)
+impl<T> Placeholder for SectionHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + num::Zero
+{
+    fn placeholder() -> Self {
+        let inner = InnerSectionHeader {
+            name_offset: 0,
+            section_type: SectionType::Null,
+            flags: T::zero(),
+            addr: T::zero(),
+            offset: T::zero(),
+            size: T::zero(),
+            link: 0,
+            info: 0,
+            addr_align: T::zero(),
+            entry_size: T::zero(),
+        };
+
+        SectionHeader { name: String::new(), inner }
     }
 }
 
@@ -99,7 +273,7 @@ struct InnerSectionHeader<T> {
 impl<T> InnerSectionHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
 {
-    fn construct(binary: &Vec<u8>, header: &Header<T>, entry: &[ops::Range<usize>], make_unsigned: impl Fn(&[u8], bool) -> T) -> Option<InnerSectionHeader<T>> {
+    fn construct(binary: &[u8], header: &Header<T>, entry: &[ops::Range<usize>], make_unsigned: impl Fn(&[u8], bool) -> T) -> Option<InnerSectionHeader<T>> {
         let name_offset = make_u32(&binary[entry[0].clone()], header.is_little()) as usize;
         let section_type = SectionType::new(make_u32(&binary[entry[1].clone()], header.is_little()))?;
         let flags = make_unsigned(&binary[entry[2].clone()], header.is_little());
@@ -124,106 +298,164 @@ impl<T> InnerSectionHeader<T>
             entry_size,
         })
     }
+
+    fn serialize(&self, is_little: bool, split_unsigned: impl Fn(T, bool) -> Vec<u8>) -> Vec<u8> {
+        let fields = [
+            split_u32(self.name_offset as u32, is_little),
+            split_u32(self.section_type.raw(), is_little),
+            split_unsigned(self.flags, is_little),
+            split_unsigned(self.addr, is_little),
+            split_unsigned(self.offset, is_little),
+            split_unsigned(self.size, is_little),
+            split_u32(self.link, is_little),
+            split_u32(self.info, is_little),
+            split_unsigned(self.addr_align, is_little),
+            split_unsigned(self.entry_size, is_little),
+        ];
+
+        fields.concat()
+    }
+}
+
+impl<T> InnerSectionHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    fn count(header: &Header<T>, entry: impl Fn(usize) -> Option<InnerSectionHeader<T>>) -> Option<usize> {
+        // with SHN_LORESERVE or more sections e_shnum is 0 and section 0's size holds the count
+        match header.sh_num() {
+            0 if header.sh_offset().as_() != 0 => Some(entry(0)?.size.as_()),
+            sh_num => Some(sh_num as usize),
+        }
+    }
+
+    fn shstrtab(header: &Header<T>, entry: impl Fn(usize) -> Option<InnerSectionHeader<T>>) -> Option<InnerSectionHeader<T>> {
+        // SHN_XINDEX means the real index didn't fit and lives in section 0's link
+        let shstrndx = match header.shstrndx() {
+            0xFFFF => entry(0)?.link as usize,
+            index => index as usize,
+        };
+
+        entry(shstrndx)
+    }
 }
 
 impl InnerSectionHeader<u32> {
-    fn make_entry_point(header: &Header<u32>, index: usize) -> usize {
+    fn make_entry_point(header: &Header<u32>, index: usize) -> Option<usize> {
         let sh_offset = header.sh_offset() as usize;
         let sh_size = header.sh_size() as usize;
 
-        sh_offset + index * sh_size
+        sh_offset.checked_add(index.checked_mul(sh_size)?)
+    }
+
+    fn entry(binary: &[u8], header: &Header<u32>, index: usize) -> Option<InnerSectionHeader<u32>> {
+        let ep = Self::make_entry_point(header, index)?;
+        let bytes = binary.get(ep..ep.checked_add(0x28)?)?;
+        let entry = [
+            0x00..0x04,
+            0x04..0x08,
+            0x08..0x0C,
+            0x0C..0x10,
+            0x10..0x14,
+            0x14..0x18,
+            0x18..0x1C,
+            0x1C..0x20,
+            0x20..0x24,
+            0x24..0x28,
+        ];
+
+        Self::construct(bytes, header, &entry, make_u32)
     }
 
-    pub fn new(binary: &Vec<u8>, header: &Header<u32>) -> Option<Vec<InnerSectionHeader<u32>>> {
-        let sh_num = header.sh_num() as usize;
+    pub fn new(binary: &[u8], header: &Header<u32>) -> Option<Vec<InnerSectionHeader<u32>>> {
+        (0..Self::checked_count(binary, header)?).map(|index| Self::entry(binary, header, index)).collect::<Option<Vec<_>>>()
+    }
 
-        (0..sh_num).map(|index| {
-            let ep = Self::make_entry_point(header, index);
-            let entry = [
-                ep + 0x00..ep + 0x04,
-                ep + 0x04..ep + 0x08,
-                ep + 0x08..ep + 0x0C,
-                ep + 0x0C..ep + 0x10,
-                ep + 0x10..ep + 0x14,
-                ep + 0x14..ep + 0x18,
-                ep + 0x18..ep + 0x1C,
-                ep + 0x1C..ep + 0x20,
-                ep + 0x20..ep + 0x24,
-                ep + 0x24..ep + 0x28,
-            ];
+    fn checked_count(binary: &[u8], header: &Header<u32>) -> Option<usize> {
+        let sh_num = Self::count(header, |index| Self::entry(binary, header, index))?;
+        // each entry needs bytes of its own, even when e_shentsize claims otherwise
+        if sh_num > binary.len() / 0x28 { return None }
 
-            Self::construct(binary, header, &entry, make_u32)
-        }).collect::<Option<Vec<_>>>()
+        Some(sh_num)
     }
 }
 
 impl InnerSectionHeader<u64> {
-    fn make_entry_point(header: &Header<u64>, index: usize) -> usize {
+    fn make_entry_point(header: &Header<u64>, index: usize) -> Option<usize> {
         let sh_offset = header.sh_offset() as usize;
         let sh_size = header.sh_size() as usize;
 
-        sh_offset + index * sh_size
+        sh_offset.checked_add(index.checked_mul(sh_size)?)
+    }
+
+    fn entry(binary: &[u8], header: &Header<u64>, index: usize) -> Option<InnerSectionHeader<u64>> {
+        let ep = Self::make_entry_point(header, index)?;
+        let bytes = binary.get(ep..ep.checked_add(0x40)?)?;
+        let entry = [
+            0x00..0x04,
+            0x04..0x08,
+            0x08..0x10,
+            0x10..0x18,
+            0x18..0x20,
+            0x20..0x28,
+            0x28..0x2C,
+            0x2C..0x30,
+            0x30..0x38,
+            0x38..0x40,
+        ];
+
+        Self::construct(bytes, header, &entry, make_u64)
     }
 
-    pub fn new(binary: &Vec<u8>, header: &Header<u64>) -> Option<Vec<InnerSectionHeader<u64>>> {
-        let sh_num = header.sh_num() as usize;
+    pub fn new(binary: &[u8], header: &Header<u64>) -> Option<Vec<InnerSectionHeader<u64>>> {
+        (0..Self::checked_count(binary, header)?).map(|index| Self::entry(binary, header, index)).collect::<Option<Vec<_>>>()
+    }
 
-        (0..sh_num).map(|index| {
-            let ep = Self::make_entry_point(header, index);
-            let entry = [
-                ep + 0x00..ep + 0x04,
-                ep + 0x04..ep + 0x08,
-                ep + 0x08..ep + 0x10,
-                ep + 0x10..ep + 0x18,
-                ep + 0x18..ep + 0x20,
-                ep + 0x20..ep + 0x28,
-                ep + 0x28..ep + 0x2C,
-                ep + 0x2C..ep + 0x30,
-                ep + 0x30..ep + 0x38,
-                ep + 0x38..ep + 0x40,
-            ];
+    fn checked_count(binary: &[u8], header: &Header<u64>) -> Option<usize> {
+        let sh_num = Self::count(header, |index| Self::entry(binary, header, index))?;
+        // each entry needs bytes of its own, even when e_shentsize claims otherwise
+        if sh_num > binary.len() / 0x40 { return None }
 
-            Self::construct(binary, header, &entry, make_u64)
-        }).collect::<Option<Vec<_>>>()
+        Some(sh_num)
     }
 }
 
-impl<T> fmt::Display for InnerSectionHeader<T>
+impl<T> InnerSectionHeader<T>
     where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn fmt_with(&self, options: &FormatOptions) -> String {
         let flag_pairs = [
-            (0x0000_0001, 'W'),
-            (0x0000_0002, 'A'),
-            (0x0000_0004, 'X'),
-            (0x0000_0010, 'M'),
-            (0x0000_0020, 'S'),
-            (0x0000_0040, 'I'),
-            (0x0000_0080, 'L'),
-            (0x0000_0100, 'O'),
-            (0x0000_0200, 'G'),
-            (0x0000_0400, 'T'),
-            (0x0FF0_0000, 'o'),
-            (0xF000_0000, 'p'),
-            (0x4000_0000, 'x'),
-            (0x8000_0000, 'x'),
+            (0x0000_0001, 'W', "WRITE"),
+            (0x0000_0002, 'A', "ALLOC"),
+            (0x0000_0004, 'X', "EXEC"),
+            (0x0000_0010, 'M', "MERGE"),
+            (0x0000_0020, 'S', "STRINGS"),
+            (0x0000_0040, 'I', "INFO LINK"),
+            (0x0000_0080, 'L', "LINK ORDER"),
+            (0x0000_0100, 'O', "OS NONCONFORMING"),
+            (0x0000_0200, 'G', "GROUP"),
+            (0x0000_0400, 'T', "TLS"),
+            (0x0FF0_0000, 'o', "OS"),
+            (0xF000_0000, 'p', "PROC"),
+            (0x4000_0000, 'x', "UNKNOWN"),
+            (0x8000_0000, 'x', "EXCLUDE"),
         ];
 
-        let flag = flag_pairs.iter().flat_map(|(mask, ch)| {
-            if mask & self.flags.as_() > 0 { Some(ch) } else { None }
-        }).collect::<String>();
+        let flags = flag_pairs.iter().filter(|(mask, _, _)| mask & self.flags.as_() > 0);
+        let flag =
+            if options.show_flags_long { flags.map(|(_, _, name)| *name).collect::<Vec<_>>().join(", ") }
+            else { flags.map(|(_, ch, _)| ch).collect::<String>() };
 
+        let width = options.hex_width.digits::<T>();
         let section_type = format!("Type:   {:?}\n", self.section_type);
         let flag         = format!("Flags:  {}\n", flag);
-        let addr         = format!("Addr:   0x{:016x}\n", self.addr);
-        let offset       = format!("Offset: 0x{:016x}\n", self.offset);
-        let size         = format!("Size:   0x{:016x}\n", self.size);
+        let addr         = format!("Addr:   0x{:01$x}\n", self.addr, width);
+        let offset       = format!("Offset: 0x{:01$x}\n", self.offset, width);
+        let size         = format!("Size:   0x{:01$x}\n", self.size, width);
         let link         = format!("Link:   {}\n", self.link);
         let info         = format!("Info:   {}\n", self.info);
         let addr_align   = format!("Align:  0x{:x}\n", self.addr_align);
 
-        write!(
-            f,
+        format!(
             "{}{}{}{}{}{}{}{}",
             section_type,
             flag,
@@ -237,7 +469,157 @@ impl<T> fmt::Display for InnerSectionHeader<T>
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+impl<T> fmt::Display for InnerSectionHeader<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.fmt_with(&FormatOptions::default()))
+    }
+}
+
+/// Reserved section header indices (`SHN_*`) that don't name a real section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecialIndex {
+    Undef,
+    LoProc(u16),
+    LoOs(u16),
+    Abs,
+    Common,
+    XIndex,
+    Reserved(u16),
+}
+
+impl SpecialIndex {
+    /// Classifies `index`, returning `None` for an ordinary section index.
+    pub fn new(index: u16) -> Option<SpecialIndex> {
+        let special = match index {
+            0x0000          => SpecialIndex::Undef,
+            0xFF00..=0xFF1F => SpecialIndex::LoProc(index - 0xFF00),
+            0xFF20..=0xFF3F => SpecialIndex::LoOs(index - 0xFF20),
+            0xFFF1          => SpecialIndex::Abs,
+            0xFFF2          => SpecialIndex::Common,
+            0xFFFF          => SpecialIndex::XIndex,
+            0xFF40..=0xFFFE => SpecialIndex::Reserved(index),
+            _               => return None,
+        };
+
+        Some(special)
+    }
+}
+
+/// A section recognized by its name, one variant per constant in `sections`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WellKnownSection {
+    Text,
+    Data,
+    Bss,
+    RoData,
+    TData,
+    TBss,
+    Init,
+    Fini,
+    InitArray,
+    FiniArray,
+    Plt,
+    Got,
+    GotPlt,
+    Dynamic,
+    DynSym,
+    DynStr,
+    Interp,
+    Hash,
+    GnuHash,
+    SymTab,
+    StrTab,
+    ShStrTab,
+    EhFrame,
+    EhFrameHdr,
+    Comment,
+    NoteGnuBuildId,
+}
+
+impl WellKnownSection {
+    pub fn new(name: &str) -> Option<WellKnownSection> {
+        let section = match name {
+            sections::TEXT              => WellKnownSection::Text,
+            sections::DATA              => WellKnownSection::Data,
+            sections::BSS               => WellKnownSection::Bss,
+            sections::RODATA            => WellKnownSection::RoData,
+            sections::TDATA             => WellKnownSection::TData,
+            sections::TBSS              => WellKnownSection::TBss,
+            sections::INIT              => WellKnownSection::Init,
+            sections::FINI              => WellKnownSection::Fini,
+            sections::INIT_ARRAY        => WellKnownSection::InitArray,
+            sections::FINI_ARRAY        => WellKnownSection::FiniArray,
+            sections::PLT               => WellKnownSection::Plt,
+            sections::GOT               => WellKnownSection::Got,
+            sections::GOT_PLT           => WellKnownSection::GotPlt,
+            sections::DYNAMIC           => WellKnownSection::Dynamic,
+            sections::DYNSYM            => WellKnownSection::DynSym,
+            sections::DYNSTR            => WellKnownSection::DynStr,
+            sections::INTERP            => WellKnownSection::Interp,
+            sections::HASH              => WellKnownSection::Hash,
+            sections::GNU_HASH          => WellKnownSection::GnuHash,
+            sections::SYMTAB            => WellKnownSection::SymTab,
+            sections::STRTAB            => WellKnownSection::StrTab,
+            sections::SHSTRTAB          => WellKnownSection::ShStrTab,
+            sections::EH_FRAME          => WellKnownSection::EhFrame,
+            sections::EH_FRAME_HDR      => WellKnownSection::EhFrameHdr,
+            sections::COMMENT           => WellKnownSection::Comment,
+            sections::NOTE_GNU_BUILD_ID => WellKnownSection::NoteGnuBuildId,
+            _                           => return None,
+        };
+
+        Some(section)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WellKnownSection::Text           => sections::TEXT,
+            WellKnownSection::Data           => sections::DATA,
+            WellKnownSection::Bss            => sections::BSS,
+            WellKnownSection::RoData         => sections::RODATA,
+            WellKnownSection::TData          => sections::TDATA,
+            WellKnownSection::TBss           => sections::TBSS,
+            WellKnownSection::Init           => sections::INIT,
+            WellKnownSection::Fini           => sections::FINI,
+            WellKnownSection::InitArray      => sections::INIT_ARRAY,
+            WellKnownSection::FiniArray      => sections::FINI_ARRAY,
+            WellKnownSection::Plt            => sections::PLT,
+            WellKnownSection::Got            => sections::GOT,
+            WellKnownSection::GotPlt         => sections::GOT_PLT,
+            WellKnownSection::Dynamic        => sections::DYNAMIC,
+            WellKnownSection::DynSym         => sections::DYNSYM,
+            WellKnownSection::DynStr         => sections::DYNSTR,
+            WellKnownSection::Interp         => sections::INTERP,
+            WellKnownSection::Hash           => sections::HASH,
+            WellKnownSection::GnuHash        => sections::GNU_HASH,
+            WellKnownSection::SymTab         => sections::SYMTAB,
+            WellKnownSection::StrTab         => sections::STRTAB,
+            WellKnownSection::ShStrTab       => sections::SHSTRTAB,
+            WellKnownSection::EhFrame        => sections::EH_FRAME,
+            WellKnownSection::EhFrameHdr     => sections::EH_FRAME_HDR,
+            WellKnownSection::Comment        => sections::COMMENT,
+            WellKnownSection::NoteGnuBuildId => sections::NOTE_GNU_BUILD_ID,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SectionCategory {
+    /// `SHF_ALLOC` and `SHF_EXECINSTR`, e.g. `.text`.
+    Code,
+    /// `SHF_ALLOC` and `SHF_WRITE` with file contents, e.g. `.data`.
+    Data,
+    /// `SHF_ALLOC` only, e.g. `.rodata`.
+    ReadOnlyData,
+    /// Allocated `NoBits`, e.g. `.bss`.
+    Bss,
+    /// Not loaded at runtime, e.g. `.symtab` or `.debug_info`.
+    Metadata,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SectionType {
     Null,
     ProgBits,
@@ -256,7 +638,12 @@ pub enum SectionType {
     PreinitArray,
     Group,
     SymTabSHNDX,
-    Num,
+    RelR,
+    GnuAttributes,
+    GnuHash,
+    VerDef,
+    VerNeed,
+    VerSym,
     Loos(u32),
     LoProc(u32),
     LoUser(u32),
@@ -284,7 +671,12 @@ impl SectionType {
             0x0000_0010 => SectionType::PreinitArray,
             0x0000_0011 => SectionType::Group,
             0x0000_0012 => SectionType::SymTabSHNDX,
-            0x0000_0013 => SectionType::Num,
+            0x0000_0013 => SectionType::RelR,
+            0x6FFF_FFF5 => SectionType::GnuAttributes,
+            0x6FFF_FFF6 => SectionType::GnuHash,
+            0x6FFF_FFFD => SectionType::VerDef,
+            0x6FFF_FFFE => SectionType::VerNeed,
+            0x6FFF_FFFF => SectionType::VerSym,
             value if range_check(value, 0x6000_0000) => SectionType::Loos(value - 0x6000_0000),
             value if range_check(value, 0x7000_0000) => SectionType::LoProc(value - 0x7000_0000),
             value if range_check(value, 0x8000_0000) => SectionType::LoUser(value - 0x8000_0000),
@@ -293,4 +685,71 @@ impl SectionType {
 
         Some(sec_type)
     }
+
+    /// The `sh_type` value, the inverse of `new`.
+    pub fn raw(&self) -> u32 {
+        match *self {
+            SectionType::Null          => 0x0000_0000,
+            SectionType::ProgBits      => 0x0000_0001,
+            SectionType::SymTab        => 0x0000_0002,
+            SectionType::StrTab        => 0x0000_0003,
+            SectionType::Rela          => 0x0000_0004,
+            SectionType::Hash          => 0x0000_0005,
+            SectionType::Dynamic       => 0x0000_0006,
+            SectionType::Note          => 0x0000_0007,
+            SectionType::NoBits        => 0x0000_0008,
+            SectionType::Rel           => 0x0000_0009,
+            SectionType::ShLib         => 0x0000_000A,
+            SectionType::DynSym        => 0x0000_000B,
+            SectionType::InitArray     => 0x0000_000E,
+            SectionType::FiniArray     => 0x0000_000F,
+            SectionType::PreinitArray  => 0x0000_0010,
+            SectionType::Group         => 0x0000_0011,
+            SectionType::SymTabSHNDX   => 0x0000_0012,
+            SectionType::RelR          => 0x0000_0013,
+            SectionType::GnuAttributes => 0x6FFF_FFF5,
+            SectionType::GnuHash       => 0x6FFF_FFF6,
+            SectionType::VerDef        => 0x6FFF_FFFD,
+            SectionType::VerNeed       => 0x6FFF_FFFE,
+            SectionType::VerSym        => 0x6FFF_FFFF,
+            SectionType::Loos(v)       => 0x6000_0000 + v,
+            SectionType::LoProc(v)     => 0x7000_0000 + v,
+            SectionType::LoUser(v)     => 0x8000_0000 + v,
+        }
+    }
+}
+
+impl fmt::Display for SectionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SectionType::Null          => "NULL".to_string(),
+            SectionType::ProgBits      => "PROGBITS".to_string(),
+            SectionType::SymTab        => "SYMTAB".to_string(),
+            SectionType::StrTab        => "STRTAB".to_string(),
+            SectionType::Rela          => "RELA".to_string(),
+            SectionType::Hash          => "HASH".to_string(),
+            SectionType::Dynamic       => "DYNAMIC".to_string(),
+            SectionType::Note          => "NOTE".to_string(),
+            SectionType::NoBits        => "NOBITS".to_string(),
+            SectionType::Rel           => "REL".to_string(),
+            SectionType::ShLib         => "SHLIB".to_string(),
+            SectionType::DynSym        => "DYNSYM".to_string(),
+            SectionType::InitArray     => "INIT_ARRAY".to_string(),
+            SectionType::FiniArray     => "FINI_ARRAY".to_string(),
+            SectionType::PreinitArray  => "PREINIT_ARRAY".to_string(),
+            SectionType::Group         => "GROUP".to_string(),
+            SectionType::SymTabSHNDX   => "SYMTAB SECTION INDICES".to_string(),
+            SectionType::RelR          => "RELR".to_string(),
+            SectionType::GnuAttributes => "GNU_ATTRIBUTES".to_string(),
+            SectionType::GnuHash       => "GNU_HASH".to_string(),
+            SectionType::VerDef        => "VERDEF".to_string(),
+            SectionType::VerNeed       => "VERNEED".to_string(),
+            SectionType::VerSym        => "VERSYM".to_string(),
+            SectionType::Loos(v)       => format!("LOOS+0x{:x}", v),
+            SectionType::LoProc(v)     => format!("LOPROC+0x{:x}", v),
+            SectionType::LoUser(v)     => format!("LOUSER+0x{:x}", v),
+        };
+
+        write!(f, "{}", name)
+    }
 }
@@ -0,0 +1,29 @@
+//! Names of the sections toolchains conventionally emit, for comparing
+//! against `SectionHeader::name` without spelling them out.
+
+pub const TEXT: &str = ".text";
+pub const DATA: &str = ".data";
+pub const BSS: &str = ".bss";
+pub const RODATA: &str = ".rodata";
+pub const TDATA: &str = ".tdata";
+pub const TBSS: &str = ".tbss";
+pub const INIT: &str = ".init";
+pub const FINI: &str = ".fini";
+pub const INIT_ARRAY: &str = ".init_array";
+pub const FINI_ARRAY: &str = ".fini_array";
+pub const PLT: &str = ".plt";
+pub const GOT: &str = ".got";
+pub const GOT_PLT: &str = ".got.plt";
+pub const DYNAMIC: &str = ".dynamic";
+pub const DYNSYM: &str = ".dynsym";
+pub const DYNSTR: &str = ".dynstr";
+pub const INTERP: &str = ".interp";
+pub const HASH: &str = ".hash";
+pub const GNU_HASH: &str = ".gnu.hash";
+pub const SYMTAB: &str = ".symtab";
+pub const STRTAB: &str = ".strtab";
+pub const SHSTRTAB: &str = ".shstrtab";
+pub const EH_FRAME: &str = ".eh_frame";
+pub const EH_FRAME_HDR: &str = ".eh_frame_hdr";
+pub const COMMENT: &str = ".comment";
+pub const NOTE_GNU_BUILD_ID: &str = ".note.gnu.build-id";
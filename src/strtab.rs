@@ -0,0 +1,43 @@
+use super::prelude::*;
+
+/// A string table whose NUL positions are found once, so resolving a name
+/// is a binary search instead of a scan to the end of the string.
+///
+/// Offsets past the table's last NUL still resolve by scanning the rest of
+/// the file, as names always have.
+pub(crate) struct StringTable<'a> {
+    binary: &'a [u8],
+    start: usize,
+    ends: Vec<usize>,
+}
+
+impl<'a> StringTable<'a> {
+    /// Indexes the `size` bytes at `start`, clamped to the end of `binary`.
+    pub(crate) fn indexed(binary: &'a [u8], start: usize, size: usize) -> StringTable<'a> {
+        let table = binary.get(start..).unwrap_or_default();
+        let table = &table[..size.min(table.len())];
+        let ends = table.iter()
+            .enumerate()
+            .filter(|&(_, &byte)| byte == 0)
+            .map(|(index, _)| start + index)
+            .collect();
+
+        StringTable { binary, start, ends }
+    }
+
+    /// A table that scans on every lookup, for callers that resolve only a few names.
+    pub(crate) fn unindexed(binary: &'a [u8], start: usize, _size: usize) -> StringTable<'a> {
+        StringTable { binary, start, ends: Vec::new() }
+    }
+
+    /// The string at `offset` in the table, without its NUL.
+    pub(crate) fn get(&self, offset: usize) -> Option<&'a [u8]> {
+        let start = self.start.checked_add(offset)?;
+        let end = match self.ends.get(self.ends.partition_point(|&end| end < start)) {
+            Some(&end) => end,
+            None => start.checked_add(self.binary.get(start..)?.iter().position(|&byte| byte == 0)?)?,
+        };
+
+        self.binary.get(start..end)
+    }
+}
@@ -0,0 +1,48 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{Class, ELF, ElfInfo, Endian, ISA, ObjectFileType, PieStatus};
+use super::prelude::*;
+
+/// The facts about a binary that a triage index records, gathered by
+/// `ELF::summary`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElfSummary {
+    pub class: Class,
+    pub endian: Endian,
+    pub isa: ISA,
+    pub object_type: ObjectFileType,
+    pub entry_point: u64,
+    pub segment_count: usize,
+    pub section_count: usize,
+    /// See `ELF::is_stripped`.
+    pub is_stripped: bool,
+    /// `pie_status` is `Pie`, which shared libraries also report.
+    pub is_pie: bool,
+    pub has_rwx: bool,
+    /// The GNU build ID as lowercase hex.
+    pub build_id: Option<String>,
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize> + cast::AsPrimitive<u64> + num::PrimInt,
+          D: AsRef<[u8]>
+{
+    pub fn summary(&self) -> ElfSummary {
+        ElfSummary {
+            class: self.class(),
+            endian: self.header.endian(),
+            isa: self.header.isa(),
+            object_type: self.header.object_type(),
+            entry_point: self.header.entry_point_u64(),
+            segment_count: self.program_headers.len(),
+            section_count: self.section_headers.len(),
+            is_stripped: self.is_stripped(),
+            is_pie: self.pie_status() == PieStatus::Pie,
+            has_rwx: self.has_rwx(),
+            build_id: self.build_id().map(|id| id.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        }
+    }
+}
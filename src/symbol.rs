@@ -0,0 +1,533 @@
+use core::fmt;
+use core::mem;
+use core::ops::Range;
+
+use super::header::{Header, ObjectFileType};
+use super::section_header::{SectionHeader, SectionType, SpecialIndex};
+use super::{ELF, VersionedSymbol};
+use super::mode::Placeholder;
+use super::strtab::StringTable;
+use super::{make_u16, make_u32, make_u64};
+
+use num::cast;
+use super::prelude::*;
+
+#[derive(Clone)]
+pub struct Symbol<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    name: String,
+    value: T,
+    size: T,
+    info: u8,
+    other: u8,
+    shndx: u16,
+    xindex: Option<u32>,
+}
+
+impl<T> Symbol<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    fn construct(
+        strings: &StringTable<'_>,
+        header: &Header<T>,
+        bytes: &[u8],
+        entry: &[Range<usize>],
+        make_unsigned: impl Fn(&[u8], bool) -> T,
+    ) -> Option<Symbol<T>> {
+        let name_offset = make_u32(&bytes[entry[0].clone()], header.is_little()) as usize;
+        let value = make_unsigned(&bytes[entry[1].clone()], header.is_little());
+        let size = make_unsigned(&bytes[entry[2].clone()], header.is_little());
+        let info = bytes[entry[3].start];
+        let other = bytes[entry[4].start];
+        let shndx = make_u16(&bytes[entry[5].clone()], header.is_little());
+
+        // names are bytes in ELF; a stray non-UTF-8 one shouldn't reject the file
+        let name = String::from_utf8_lossy(strings.get(name_offset)?).into_owned();
+
+        Some(Symbol {
+            name,
+            value,
+            size,
+            info,
+            other,
+            shndx,
+            xindex: None,
+        })
+    }
+
+    /// Fills in the real section index of an `SHN_XINDEX` symbol from the
+    /// `index`th word of the `SHT_SYMTAB_SHNDX` table.
+    fn resolve_xindex(mut self, table: Option<&[u8]>, index: usize, is_little: bool) -> Symbol<T> {
+        if self.shndx == 0xFFFF {
+            let word = index.checked_mul(4).and_then(|start| table?.get(start..start.checked_add(4)?));
+            self.xindex = word.map(|word| make_u32(word, is_little));
+        }
+
+        self
+    }
+
+    // the `SHT_SYMTAB_SHNDX` section whose `sh_link` names `symtab`
+    fn extended_indices<'a>(binary: &'a [u8], sections: &[SectionHeader<T>], symtab: &SectionHeader<T>) -> Option<&'a [u8]> {
+        let is_symtab = |sh: &SectionHeader<T>| {
+            sh.section_type() == symtab.section_type() && sh.file_offset().as_() == symtab.file_offset().as_()
+        };
+        let table = sections.iter()
+            .filter(|sh| sh.section_type() == SectionType::SymTabSHNDX)
+            .find(|sh| sections.get(sh.link() as usize).is_some_and(is_symtab))?;
+
+        binary.get(table.file_range(binary.len())?)
+    }
+
+    fn entries<'a>(
+        binary: &'a [u8],
+        sections: &[SectionHeader<T>],
+        symtab: &SectionHeader<T>,
+        entry_size: usize,
+        strings: fn(&'a [u8], usize, usize) -> StringTable<'a>,
+    ) -> Option<(StringTable<'a>, impl Iterator<Item = usize>)> {
+        let strtab = sections.get(symtab.link() as usize)?;
+        let strtab = strings(binary, strtab.file_offset().as_(), strtab.size().as_());
+        let entry_size = match symtab.entry_size().as_() {
+            0 => entry_size,
+            size => size,
+        };
+        let start = symtab.file_offset().as_();
+        let count = symtab.size().as_() / entry_size;
+        // reject tables running past the file before handing out entry offsets
+        if start.checked_add(count * entry_size)? > binary.len() { return None }
+
+        Some((strtab, (0..count).map(move |index| start + index * entry_size)))
+    }
+
+    pub fn name(&self) -> String { self.name.clone() }
+    pub(crate) fn name_str(&self) -> &str { &self.name }
+    pub fn value(&self) -> T { self.value }
+    pub fn size(&self) -> T { self.size }
+    pub fn info(&self) -> u8 { self.info }
+    pub fn other(&self) -> u8 { self.other }
+    /// The raw `st_shndx`, which is `SHN_XINDEX` (0xFFFF) for symbols whose
+    /// section index didn't fit; see `section_index`.
+    pub fn shndx(&self) -> u16 { self.shndx }
+
+    /// The index of the section the symbol is defined in, looking up
+    /// `SHN_XINDEX` symbols in `SHT_SYMTAB_SHNDX`. `None` for undefined,
+    /// absolute, common and other reserved indices.
+    pub fn section_index(&self) -> Option<usize> {
+        match self.xindex {
+            Some(index) => Some(index as usize),
+            None if SpecialIndex::new(self.shndx).is_some() => None,
+            None => Some(self.shndx as usize),
+        }
+    }
+
+    pub fn binding(&self) -> SymbolBinding { SymbolBinding::new(self.info >> 4) }
+    pub fn sym_type(&self) -> SymbolType { SymbolType::new(self.info & 0x0F) }
+    pub fn visibility(&self) -> Visibility { Visibility::new(self.other) }
+
+    /// Returns the `size` bytes the symbol covers in its `ProgBits` section.
+    ///
+    /// `None` for zero-sized, undefined, absolute or common symbols, and when
+    /// the symbol doesn't lie within its section.
+    pub fn data<'a, D: AsRef<[u8]>>(&self, elf: &'a ELF<T, D>) -> Option<&'a [u8]> {
+        let size = self.size.as_();
+        if size == 0 { return None }
+
+        let section = elf.section(self.section_index()?)?;
+        if section.section_type() != SectionType::ProgBits { return None }
+
+        // relocatable objects store section offsets, everything else addresses
+        let offset =
+            if elf.header().object_type() == ObjectFileType::REL { self.value.as_() }
+            else { self.value.as_().checked_sub(section.target_addr().as_())? };
+        if offset.checked_add(size)? > section.size().as_() { return None }
+
+        let start = section.file_range(elf.data().len())?.start + offset;
+        elf.data().get(start..start + size)
+    }
+}
+
+impl<T> fmt::Display for Symbol<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    /// Writes the symbol as a `readelf -sW` row, minus the leading `Num:` column.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // readelf switches to hex once the size no longer fits five digits
+        let size = match self.size.as_() {
+            size @ 0..=99999 => format!("{:5}", size),
+            _ => format!("{:#x}", self.size),
+        };
+        let ndx = self.xindex.map_or_else(|| index_name(self.shndx), |index| index.to_string());
+
+        write!(
+            f, "{:0width$x} {} {:<7} {:<6} {:<7} {:>4} {}",
+            self.value, size, type_name(self.sym_type()), binding_name(self.binding()),
+            visibility_name(self.visibility()), ndx, self.name,
+            width = mem::size_of::<T>() * 2,
+        )
+    }
+}
+
+impl<T> Placeholder for Symbol<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + num::Zero
+{
+    fn placeholder() -> Self {
+        Symbol { name: String::new(), value: T::zero(), size: T::zero(), info: 0, other: 0, shndx: 0, xindex: None }
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Prints `.dynsym` and `.symtab` the way `readelf -sW` does, with
+    /// section symbols named after their section and `.dynsym` names
+    /// carrying their versions.
+    pub fn format_symbols(&self) -> String {
+        let tables = self.section_headers.iter()
+            .filter_map(|sh| match sh.section_type() {
+                SectionType::DynSym => Some(self.format_symbol_table(sh, &self.dynamic_symbols, &self.symbol_versions())),
+                SectionType::SymTab => Some(self.format_symbol_table(sh, &self.symbols, &[])),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if tables.is_empty() { "Dynamic symbol information is not available for displaying symbols.\n".to_string() }
+        else { tables.join("\n") }
+    }
+
+    fn format_symbol_table(&self, section: &SectionHeader<T>, symbols: &[Symbol<T>], versions: &[VersionedSymbol]) -> String {
+        let mut lines = vec![
+            format!("Symbol table '{}' contains {} {}:", section.name(), symbols.len(), if symbols.len() == 1 { "entry" } else { "entries" }),
+            match mem::size_of::<T>() {
+                8 => "   Num:    Value          Size Type    Bind   Vis      Ndx Name",
+                _ => "   Num:    Value  Size Type    Bind   Vis      Ndx Name",
+            }.to_string(),
+        ];
+
+        for (index, sym) in symbols.iter().enumerate() {
+            let mut sym = sym.clone();
+            if sym.sym_type() == SymbolType::Section && sym.name.is_empty() {
+                sym.name = sym.section_index().and_then(|index| self.section(index)).map(|sh| sh.name()).unwrap_or_default();
+            }
+
+            // required versions name the `.gnu.version` index they were bound
+            // through, defined ones mark the default version with `@@`; the
+            // absolute symbol naming a defined version gets no suffix
+            if let Some(versioned) = versions.get(index) {
+                match versioned.version() {
+                    Some(version) if versioned.required() =>
+                        sym.name += &format!("@{} ({})", version, versioned.index()),
+                    Some(version) if version != sym.name =>
+                        sym.name += &format!("{}{}", versioned.separator(), version),
+                    _ => {}
+                }
+            }
+
+            lines.push(format!("{:>6}: {}", index, sym));
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+fn type_name(sym_type: SymbolType) -> String {
+    match sym_type {
+        SymbolType::NoType => "NOTYPE".to_string(),
+        SymbolType::Object => "OBJECT".to_string(),
+        SymbolType::Func => "FUNC".to_string(),
+        SymbolType::Section => "SECTION".to_string(),
+        SymbolType::File => "FILE".to_string(),
+        SymbolType::Common => "COMMON".to_string(),
+        SymbolType::Tls => "TLS".to_string(),
+        SymbolType::Loos(0) => "IFUNC".to_string(),
+        SymbolType::Loos(value) => format!("<OS specific>: {}", value + 0xA),
+        SymbolType::Loproc(value) => format!("<processor specific>: {}", value + 0xD),
+        SymbolType::Unknown(value) => format!("<unknown>: {}", value),
+    }
+}
+
+fn binding_name(binding: SymbolBinding) -> String {
+    match binding {
+        SymbolBinding::Local => "LOCAL".to_string(),
+        SymbolBinding::Global => "GLOBAL".to_string(),
+        SymbolBinding::Weak => "WEAK".to_string(),
+        SymbolBinding::GnuUnique => "UNIQUE".to_string(),
+        SymbolBinding::Loos(value) => format!("<OS specific>: {}", value + 0xA),
+        SymbolBinding::Loproc(value) => format!("<processor specific>: {}", value + 0xD),
+        SymbolBinding::Unknown(value) => format!("<unknown>: {}", value),
+    }
+}
+
+fn visibility_name(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Default => "DEFAULT",
+        Visibility::Internal => "INTERNAL",
+        Visibility::Hidden => "HIDDEN",
+        Visibility::Protected => "PROTECTED",
+    }
+}
+
+fn index_name(shndx: u16) -> String {
+    match SpecialIndex::new(shndx) {
+        None => shndx.to_string(),
+        Some(SpecialIndex::Undef) => "UND".to_string(),
+        Some(SpecialIndex::Abs) => "ABS".to_string(),
+        Some(SpecialIndex::Common) => "COM".to_string(),
+        Some(SpecialIndex::LoProc(_)) => format!("PRC[0x{:04x}]", shndx),
+        Some(SpecialIndex::LoOs(_)) => format!("OS [0x{:04x}]", shndx),
+        Some(SpecialIndex::XIndex | SpecialIndex::Reserved(_)) => format!("RSV[0x{:04x}]", shndx),
+    }
+}
+
+#[cfg(feature = "demangle")]
+impl<T> Symbol<T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    /// Demangles Rust (legacy `_ZN..E` and v0 `_R..`) and C++ Itanium (`_Z..`)
+    /// names, returning the raw name if it is not a recognized mangling.
+    pub fn demangle(&self) -> String {
+        if let Ok(demangled) = rustc_demangle::try_demangle(&self.name) {
+            return format!("{:#}", demangled);
+        }
+
+        if self.name.starts_with("_Z") {
+            let demangled = cpp_demangle::Symbol::new(self.name.as_bytes())
+                .ok()
+                .and_then(|sym| sym.demangle(&Default::default()).ok());
+
+            if let Some(demangled) = demangled {
+                return demangled;
+            }
+        }
+
+        self.name.clone()
+    }
+}
+
+impl Symbol<u32> {
+    pub fn new(binary: &[u8], header: &Header<u32>, sections: &[SectionHeader<u32>], symtab: &SectionHeader<u32>) -> Option<Vec<Symbol<u32>>> {
+        Self::each(binary, header, sections, symtab)?.collect()
+    }
+
+    // every entry, `None` for those that can't be decoded; `None` overall
+    // when the table or its string table can't be located
+    pub(crate) fn each<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u32>,
+        sections: &'a [SectionHeader<u32>],
+        symtab: &SectionHeader<u32>,
+    ) -> Option<impl Iterator<Item = Option<Symbol<u32>>> + 'a> {
+        Self::each_with(binary, header, sections, symtab, StringTable::indexed)
+    }
+
+    fn each_with<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u32>,
+        sections: &'a [SectionHeader<u32>],
+        symtab: &SectionHeader<u32>,
+        strings: fn(&'a [u8], usize, usize) -> StringTable<'a>,
+    ) -> Option<impl Iterator<Item = Option<Symbol<u32>>> + 'a> {
+        let (strtab, entry_points) = Self::entries(binary, sections, symtab, 0x10, strings)?;
+        let xindices = Self::extended_indices(binary, sections, symtab);
+
+        Some(entry_points.enumerate().map(move |(index, ep)| {
+            Self::entry(binary, header, &strtab, ep).map(|sym| sym.resolve_xindex(xindices, index, header.is_little()))
+        }))
+    }
+
+    /// Like `new`, but decodes each entry only when the iterator reaches it.
+    /// Iteration stops at the first entry that can't be decoded.
+    pub fn lazy<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u32>,
+        sections: &'a [SectionHeader<u32>],
+        symtab: &SectionHeader<u32>,
+    ) -> impl Iterator<Item = Symbol<u32>> + 'a {
+        // indexing the whole string table up front would cost what laziness saves
+        Self::each_with(binary, header, sections, symtab, StringTable::unindexed).into_iter().flatten().map_while(|sym| sym)
+    }
+
+    fn entry(binary: &[u8], header: &Header<u32>, strtab: &StringTable<'_>, ep: usize) -> Option<Symbol<u32>> {
+        let bytes = binary.get(ep..ep.checked_add(0x10)?)?;
+        let entry = [
+            0x00..0x04,
+            0x04..0x08,
+            0x08..0x0C,
+            0x0C..0x0D,
+            0x0D..0x0E,
+            0x0E..0x10,
+        ];
+
+        Self::construct(strtab, header, bytes, &entry, make_u32)
+    }
+}
+
+impl Symbol<u64> {
+    pub fn new(binary: &[u8], header: &Header<u64>, sections: &[SectionHeader<u64>], symtab: &SectionHeader<u64>) -> Option<Vec<Symbol<u64>>> {
+        Self::each(binary, header, sections, symtab)?.collect()
+    }
+
+    // every entry, `None` for those that can't be decoded; `None` overall
+    // when the table or its string table can't be located
+    pub(crate) fn each<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u64>,
+        sections: &'a [SectionHeader<u64>],
+        symtab: &SectionHeader<u64>,
+    ) -> Option<impl Iterator<Item = Option<Symbol<u64>>> + 'a> {
+        Self::each_with(binary, header, sections, symtab, StringTable::indexed)
+    }
+
+    fn each_with<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u64>,
+        sections: &'a [SectionHeader<u64>],
+        symtab: &SectionHeader<u64>,
+        strings: fn(&'a [u8], usize, usize) -> StringTable<'a>,
+    ) -> Option<impl Iterator<Item = Option<Symbol<u64>>> + 'a> {
+        let (strtab, entry_points) = Self::entries(binary, sections, symtab, 0x18, strings)?;
+        let xindices = Self::extended_indices(binary, sections, symtab);
+
+        Some(entry_points.enumerate().map(move |(index, ep)| {
+            Self::entry(binary, header, &strtab, ep).map(|sym| sym.resolve_xindex(xindices, index, header.is_little()))
+        }))
+    }
+
+    /// Like `new`, but decodes each entry only when the iterator reaches it.
+    /// Iteration stops at the first entry that can't be decoded.
+    pub fn lazy<'a>(
+        binary: &'a [u8],
+        header: &'a Header<u64>,
+        sections: &'a [SectionHeader<u64>],
+        symtab: &SectionHeader<u64>,
+    ) -> impl Iterator<Item = Symbol<u64>> + 'a {
+        // indexing the whole string table up front would cost what laziness saves
+        Self::each_with(binary, header, sections, symtab, StringTable::unindexed).into_iter().flatten().map_while(|sym| sym)
+    }
+
+    fn entry(binary: &[u8], header: &Header<u64>, strtab: &StringTable<'_>, ep: usize) -> Option<Symbol<u64>> {
+        let bytes = binary.get(ep..ep.checked_add(0x18)?)?;
+        let entry = [
+            0x00..0x04,
+            0x08..0x10,
+            0x10..0x18,
+            0x04..0x05,
+            0x05..0x06,
+            0x06..0x08,
+        ];
+
+        Self::construct(strtab, header, bytes, &entry, make_u64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SymbolStats {
+    pub defined: usize,
+    pub undefined: usize,
+    pub local: usize,
+    pub global: usize,
+    pub weak: usize,
+}
+
+impl SymbolStats {
+    pub fn new<T>(symbols: &[Symbol<T>]) -> SymbolStats
+        where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+    {
+        // index 0 is the reserved STN_UNDEF entry, not a real symbol
+        symbols.iter().skip(1).fold(SymbolStats::default(), |mut stats, sym| {
+            if sym.shndx() == 0 { stats.undefined += 1 } else { stats.defined += 1 }
+
+            match sym.binding() {
+                SymbolBinding::Local => stats.local += 1,
+                SymbolBinding::Global => stats.global += 1,
+                SymbolBinding::Weak => stats.weak += 1,
+                _ => {}
+            }
+
+            stats
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolBinding {
+    Local,
+    Global,
+    Weak,
+    /// `STB_GNU_UNIQUE`: one definition per process even across `dlopen`ed
+    /// objects with local scope.
+    GnuUnique,
+    Loos(u8),
+    Loproc(u8),
+    Unknown(u8),
+}
+
+impl SymbolBinding {
+    pub fn new(value: u8) -> SymbolBinding {
+        match value {
+            0x0 => SymbolBinding::Local,
+            0x1 => SymbolBinding::Global,
+            0x2 => SymbolBinding::Weak,
+            0xA => SymbolBinding::GnuUnique,
+            0xB..=0xC => SymbolBinding::Loos(value - 0xA),
+            0xD..=0xF => SymbolBinding::Loproc(value - 0xD),
+            _ => SymbolBinding::Unknown(value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymbolType {
+    NoType,
+    Object,
+    Func,
+    Section,
+    File,
+    Common,
+    Tls,
+    Loos(u8),
+    Loproc(u8),
+    Unknown(u8),
+}
+
+impl SymbolType {
+    pub fn new(value: u8) -> SymbolType {
+        match value {
+            0x0 => SymbolType::NoType,
+            0x1 => SymbolType::Object,
+            0x2 => SymbolType::Func,
+            0x3 => SymbolType::Section,
+            0x4 => SymbolType::File,
+            0x5 => SymbolType::Common,
+            0x6 => SymbolType::Tls,
+            0xA..=0xC => SymbolType::Loos(value - 0xA),
+            0xD..=0xF => SymbolType::Loproc(value - 0xD),
+            _ => SymbolType::Unknown(value),
+        }
+    }
+}
+
+/// Where a symbol can be referenced from, per the low two bits of `st_other`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    /// Follows the symbol's binding: global and weak symbols are exported.
+    Default,
+    /// Hidden, and further restricted by processor-specific rules.
+    Internal,
+    /// Not visible outside the component that defines it.
+    Hidden,
+    /// Exported, but references from within the component aren't preemptible.
+    Protected,
+}
+
+impl Visibility {
+    pub fn new(other: u8) -> Visibility {
+        match other & 0x3 {
+            0x0 => Visibility::Default,
+            0x1 => Visibility::Internal,
+            0x2 => Visibility::Hidden,
+            _ => Visibility::Protected,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::mem;
+
+use num::cast;
+
+use super::{ELF, ProgramType};
+use super::{make_u16, make_u32, make_u64};
+
+const DW_EH_PE_OMIT: u8 = 0xFF;
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Whether the file carries `.eh_frame` unwind tables, either located
+    /// through a `PT_GNU_EH_FRAME` segment or as an `.eh_frame` section.
+    pub fn has_unwind_info(&self) -> bool {
+        self.program_headers.iter().any(|ph| ph.program_type() == ProgramType::GnuEhFrame)
+            || self.section_headers.iter().any(|sh| sh.name() == ".eh_frame" && sh.size().as_() != 0)
+    }
+
+    /// Returns the number of entries in the `.eh_frame_hdr` binary search table.
+    ///
+    /// Only the fixed header is decoded. `None` without a `PT_GNU_EH_FRAME`
+    /// segment, for an unknown version, or when the table is omitted.
+    pub fn eh_frame_hdr_entries(&self) -> Option<usize> {
+        let segment = self.program_headers.iter().find(|ph| ph.program_type() == ProgramType::GnuEhFrame)?;
+        let start = segment.offset().as_();
+        let mut offset = start.checked_add(4)?;
+        let header = self.data().get(start..offset)?;
+
+        let (version, eh_frame_ptr_enc, fde_count_enc) = (header[0], header[1], header[2]);
+        if version != 1 || fde_count_enc == DW_EH_PE_OMIT { return None }
+
+        let is_little = self.header.is_little();
+        let word_size = mem::size_of::<T>();
+        if eh_frame_ptr_enc != DW_EH_PE_OMIT {
+            read_encoded(self.data(), &mut offset, eh_frame_ptr_enc, word_size, is_little)?;
+        }
+
+        let count = read_encoded(self.data(), &mut offset, fde_count_enc, word_size, is_little)?;
+        usize::try_from(count).ok()
+    }
+}
+
+/// Reads a `DW_EH_PE_*` encoded value at `offset` and advances past it.
+/// Only the value format is honoured; the application bits (pcrel, datarel, ...)
+/// don't matter for a count.
+fn read_encoded(data: &[u8], offset: &mut usize, encoding: u8, word_size: usize, is_little: bool) -> Option<u64> {
+    let mut fixed = |size: usize| {
+        let end = offset.checked_add(size)?;
+        let bytes = data.get(*offset..end)?;
+        *offset = end;
+        Some(bytes)
+    };
+
+    let value = match encoding & 0x0F {
+        0x00 if word_size == 4 => make_u32(fixed(4)?, is_little) as u64,
+        0x00 => make_u64(fixed(8)?, is_little),
+        0x01 | 0x09 => read_leb128(data, offset)?,
+        0x02 | 0x0A => make_u16(fixed(2)?, is_little) as u64,
+        0x03 | 0x0B => make_u32(fixed(4)?, is_little) as u64,
+        0x04 | 0x0C => make_u64(fixed(8)?, is_little),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn read_leb128(data: &[u8], offset: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+
+    loop {
+        let byte = *data.get(*offset)?;
+        *offset += 1;
+
+        if shift < 64 { value |= ((byte & 0x7F) as u64) << shift }
+        shift += 7;
+        if byte & 0x80 == 0 { return Some(value) }
+    }
+}
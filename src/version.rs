@@ -0,0 +1,140 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+use core::ops;
+
+use num::cast;
+
+use super::{ELF, SectionHeader, SectionType};
+use super::{make_string, make_u16, make_u32};
+use super::prelude::*;
+
+/// A dynamic symbol paired with the version it defines or requires,
+/// e.g. `memcpy` with `GLIBC_2.14`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionedSymbol {
+    name: String,
+    version: Option<String>,
+    index: u16,
+    hidden: bool,
+    required: bool,
+}
+
+impl VersionedSymbol {
+    pub fn name(&self) -> String { self.name.clone() }
+    pub fn version(&self) -> Option<String> { self.version.clone() }
+    /// The raw `.gnu.version` index, without the hidden bit.
+    pub fn index(&self) -> u16 { self.index }
+    pub fn hidden(&self) -> bool { self.hidden }
+    /// Whether the version is one a dependency provides (`.gnu.version_r`)
+    /// rather than one this object defines (`.gnu.version_d`).
+    pub fn required(&self) -> bool { self.required }
+
+    // required versions and hidden definitions take `@`; only the default
+    // version this object defines takes `@@`
+    pub(crate) fn separator(&self) -> &'static str {
+        if self.required || self.hidden { "@" } else { "@@" }
+    }
+}
+
+impl<T, D> ELF<T, D>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>,
+          D: AsRef<[u8]>
+{
+    /// Pairs every `.dynsym` entry with its version from `.gnu.version`,
+    /// resolving version names through `.gnu.version_r` and `.gnu.version_d`.
+    ///
+    /// Local and global (unversioned) entries get `None` as their version.
+    pub fn symbol_versions(&self) -> Vec<VersionedSymbol> {
+        let versym = match self.section_headers.iter().find(|sh| sh.section_type() == SectionType::VerSym) {
+            Some(section) => section,
+            None => return Vec::new(),
+        };
+
+        let mut names = BTreeMap::new();
+        for section in self.section_headers.iter() {
+            match section.section_type() {
+                SectionType::VerNeed => self.read_verneed(section, &mut names),
+                SectionType::VerDef => self.read_verdef(section, &mut names),
+                _ => None,
+            };
+        }
+
+        let is_little = self.header.is_little();
+        let start = versym.file_offset().as_();
+
+        self.dynamic_symbols.iter().zip(0usize..).map(|(sym, index)| {
+            // an unreadable slot reads as local, so the symbols after it keep their versions
+            let value = index.checked_mul(2)
+                .and_then(|offset| self.field(start, offset..offset + 2))
+                .map_or(0, |bytes| make_u16(bytes, is_little));
+            let version = match value & 0x7FFF {
+                0 | 1 => None,
+                index => names.get(&index).cloned(),
+            };
+            let (version, required) = match version {
+                Some((name, required)) => (Some(name), required),
+                None => (None, false),
+            };
+
+            VersionedSymbol {
+                name: sym.name(),
+                version,
+                index: value & 0x7FFF,
+                hidden: value & 0x8000 != 0,
+                required,
+            }
+        }).collect()
+    }
+
+    // the bytes at `range` past `base`, `None` when the offsets overflow or leave the file
+    fn field(&self, base: usize, range: ops::Range<usize>) -> Option<&[u8]> {
+        self.data().get(base.checked_add(range.start)?..base.checked_add(range.end)?)
+    }
+
+    fn read_verneed(&self, section: &SectionHeader<T>, names: &mut BTreeMap<u16, (String, bool)>) -> Option<()> {
+        let is_little = self.header.is_little();
+        let strtab = self.section_headers.get(section.link() as usize)?.file_offset().as_();
+        let mut entry = section.file_offset().as_();
+
+        // the entry count lives in the section's info field
+        for _ in 0..section.info() {
+            let count = make_u16(self.field(entry, 0x02..0x04)?, is_little);
+            let mut aux = entry.checked_add(make_u32(self.field(entry, 0x08..0x0C)?, is_little) as usize)?;
+
+            for _ in 0..count {
+                let other = make_u16(self.field(aux, 0x06..0x08)?, is_little);
+                let name = make_u32(self.field(aux, 0x08..0x0C)?, is_little) as usize;
+                names.insert(other & 0x7FFF, (make_string(self.data(), strtab.checked_add(name)?)?, true));
+
+                aux = aux.checked_add(make_u32(self.field(aux, 0x0C..0x10)?, is_little) as usize)?;
+            }
+
+            match make_u32(self.field(entry, 0x0C..0x10)?, is_little) {
+                0 => break,
+                next => entry = entry.checked_add(next as usize)?,
+            }
+        }
+
+        Some(())
+    }
+
+    fn read_verdef(&self, section: &SectionHeader<T>, names: &mut BTreeMap<u16, (String, bool)>) -> Option<()> {
+        let is_little = self.header.is_little();
+        let strtab = self.section_headers.get(section.link() as usize)?.file_offset().as_();
+        let mut entry = section.file_offset().as_();
+
+        for _ in 0..section.info() {
+            let index = make_u16(self.field(entry, 0x04..0x06)?, is_little);
+            let aux = entry.checked_add(make_u32(self.field(entry, 0x0C..0x10)?, is_little) as usize)?;
+            let name = make_u32(self.field(aux, 0x00..0x04)?, is_little) as usize;
+            names.insert(index & 0x7FFF, (make_string(self.data(), strtab.checked_add(name)?)?, false));
+
+            match make_u32(self.field(entry, 0x10..0x14)?, is_little) {
+                0 => break,
+                next => entry = entry.checked_add(next as usize)?,
+            }
+        }
+
+        Some(())
+    }
+}
@@ -0,0 +1,61 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{Header, ProgramHeader, ProgramType, SectionHeader, SectionType};
+use super::prelude::*;
+
+/// Header fields that disagree with each other or with the file, which
+/// parsing tolerates but a well-formed binary would not contain.
+///
+/// `ph_entry_size` and `sh_entry_size` are the entry sizes of the class.
+pub(crate) fn collect<T>(
+    data: &[u8],
+    header: &Header<T>,
+    program_headers: &[ProgramHeader<T>],
+    section_headers: &[SectionHeader<T>],
+    ph_entry_size: u16,
+    sh_entry_size: u16,
+) -> Vec<String>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    let mut warnings = Vec::new();
+
+    if header.ph_num() != 0 && header.ph_size() != ph_entry_size {
+        warnings.push(format!("e_phentsize={} but program headers are {} bytes", header.ph_size(), ph_entry_size));
+    }
+    if header.ph_num() == 0 && header.ph_offset().as_() != 0 {
+        warnings.push(format!("e_phnum=0 but e_phoff=0x{:x}", header.ph_offset()));
+    }
+
+    if !section_headers.is_empty() && header.sh_size() != sh_entry_size {
+        warnings.push(format!("e_shentsize={} but section headers are {} bytes", header.sh_size(), sh_entry_size));
+    }
+    if header.sh_num() == 0 && header.sh_offset().as_() != 0 && section_headers.is_empty() {
+        warnings.push("e_shnum=0 but e_shoff!=0 (SHN_XINDEX expected)".to_string());
+    }
+
+    // 0xFFFF (SHN_XINDEX) defers the index to section 0's sh_link
+    let shstrndx = header.shstrndx() as usize;
+    if !section_headers.is_empty() && shstrndx != 0xFFFF && shstrndx >= section_headers.len() {
+        warnings.push(format!("e_shstrndx={} but there are {} sections", shstrndx, section_headers.len()));
+    }
+
+    for (index, ph) in program_headers.iter().enumerate() {
+        let end = ph.offset().as_().checked_add(ph.file_size().as_());
+        if ph.program_type() != ProgramType::Null && end.is_none_or(|end| end > data.len()) {
+            warnings.push(format!("segment {} extends past the end of the file", index));
+        }
+    }
+
+    for (index, sh) in section_headers.iter().enumerate() {
+        let outside = sh.section_type() != SectionType::Null
+            && sh.section_type() != SectionType::NoBits
+            && sh.file_range(data.len()).is_none();
+        if outside {
+            warnings.push(format!("section {} ({}) extends past the end of the file", index, sh.name()));
+        }
+    }
+
+    warnings
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+
+use num::cast;
+
+use super::{ELF, Header, ProgramHeader, SectionHeader, SectionType};
+use super::prelude::*;
+
+// header offsets come from the caller or a parsed file, so an image past
+// 4 GiB is taken as a bogus offset rather than zero-filled
+const MAX_IMAGE_LEN: u64 = 0x1_0000_0000;
+
+/// Serializes a header, its program headers and section contents back into
+/// an ELF image, placing each piece at the file offset its header records.
+pub struct ElfWriter<'a, T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy
+{
+    header: &'a Header<T>,
+    program_headers: &'a [ProgramHeader<T>],
+    sections: Vec<(&'a SectionHeader<T>, &'a [u8])>,
+}
+
+impl<'a, T> ElfWriter<'a, T>
+    where T: fmt::Display + fmt::Debug + fmt::LowerHex + Copy + cast::AsPrimitive<usize>
+{
+    pub fn new(header: &'a Header<T>, program_headers: &'a [ProgramHeader<T>]) -> ElfWriter<'a, T> {
+        ElfWriter {
+            header,
+            program_headers,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn from_elf<D: AsRef<[u8]>>(elf: &'a ELF<T, D>) -> ElfWriter<'a, T> {
+        let writer = ElfWriter::new(elf.header(), elf.program_headers());
+
+        elf.section_headers().iter().fold(writer, |writer, sh| {
+            let data = sh.file_range(elf.data().len()).map(|range| &elf.data()[range]);
+
+            writer.section(sh, data.unwrap_or(&[]))
+        })
+    }
+
+    /// Appends a section header together with the bytes stored at its file offset.
+    /// Sections are written in the order they are added. `SHT_NOBITS` sections
+    /// occupy no file space, so only their header is written.
+    pub fn section(mut self, section: &'a SectionHeader<T>, data: &'a [u8]) -> ElfWriter<'a, T> {
+        self.sections.push((section, data));
+        self
+    }
+
+    fn assemble(&self, header: Vec<u8>, program_headers: Vec<Vec<u8>>, section_headers: Vec<Vec<u8>>) -> Option<Vec<u8>> {
+        let ph_offset = self.header.ph_offset().as_();
+        let ph_size = self.header.ph_size() as usize;
+        let sh_offset = self.header.sh_offset().as_();
+        let sh_size = self.header.sh_size() as usize;
+        let table_entry = |start: usize, size: usize, index: usize| index.checked_mul(size)?.checked_add(start);
+
+        let mut pieces = vec![(0, header)];
+        for (bytes, index) in program_headers.into_iter().zip(0..) {
+            pieces.push((table_entry(ph_offset, ph_size, index)?, bytes));
+        }
+        pieces.extend(self.sections.iter()
+            .filter(|(sh, _)| sh.section_type() != SectionType::NoBits)
+            .map(|(sh, data)| (sh.file_offset().as_(), data.to_vec())));
+        for (bytes, index) in section_headers.into_iter().zip(0..) {
+            pieces.push((table_entry(sh_offset, sh_size, index)?, bytes));
+        }
+
+        let ends = pieces.iter().map(|(offset, bytes)| offset.checked_add(bytes.len())).collect::<Option<Vec<_>>>()?;
+        let length = ends.into_iter().max().unwrap_or(0);
+        if length as u64 > MAX_IMAGE_LEN { return None }
+
+        let mut binary = Vec::new();
+        binary.try_reserve_exact(length).ok()?;
+        binary.resize(length, 0);
+        for (offset, bytes) in pieces {
+            binary[offset..offset + bytes.len()].copy_from_slice(&bytes);
+        }
+
+        Some(binary)
+    }
+}
+
+impl<'a> ElfWriter<'a, u32> {
+    /// Serializes the image. `None` when a header or section offset overflows
+    /// or would make the image larger than 4 GiB.
+    pub fn write(&self) -> Option<Vec<u8>> {
+        let is_little = self.header.is_little();
+        let program_headers = self.program_headers.iter().map(|ph| ph.to_bytes(is_little)).collect();
+        let section_headers = self.sections.iter().map(|(sh, _)| sh.to_bytes(is_little)).collect();
+
+        self.assemble(self.header.to_bytes(), program_headers, section_headers)
+    }
+}
+
+impl<'a> ElfWriter<'a, u64> {
+    /// Serializes the image. `None` when a header or section offset overflows
+    /// or would make the image larger than 4 GiB.
+    pub fn write(&self) -> Option<Vec<u8>> {
+        let is_little = self.header.is_little();
+        let program_headers = self.program_headers.iter().map(|ph| ph.to_bytes(is_little)).collect();
+        let section_headers = self.sections.iter().map(|(sh, _)| sh.to_bytes(is_little)).collect();
+
+        self.assemble(self.header.to_bytes(), program_headers, section_headers)
+    }
+}